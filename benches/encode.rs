@@ -10,7 +10,7 @@ fn encode_benchmark(c: &mut Criterion) {
     });
 
     c.bench_function("encode into 5111", |b| {
-        let mut buffer = String::with_capacity(13);
+        let mut buffer = String::with_capacity(crockford::MAX_ENCODED_LEN_U64);
         b.iter(|| {
             buffer.clear();
             crockford::encode_into(black_box(5111), &mut buffer);
@@ -18,12 +18,38 @@ fn encode_benchmark(c: &mut Criterion) {
     });
 
     c.bench_function("encode into 184long", |b| {
-        let mut buffer = String::with_capacity(13);
+        let mut buffer = String::with_capacity(crockford::MAX_ENCODED_LEN_U64);
         b.iter(|| {
             buffer.clear();
             crockford::encode_into(black_box(18446744073709551615), &mut buffer);
         })
     });
+
+    // A spread of lengths, so neither path gets to predict every value's digit count.
+    let values: Vec<u64> = (0..10_000u64)
+        .map(|n| n.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> (n % 64))
+        .collect();
+
+    c.bench_function("encode into 10k joined", |b| {
+        let mut buffer = String::new();
+        b.iter(|| {
+            buffer.clear();
+            for (idx, &n) in black_box(&values).iter().enumerate() {
+                if idx > 0 {
+                    buffer.push(',');
+                }
+                crockford::encode_into(n, &mut buffer);
+            }
+        })
+    });
+
+    c.bench_function("encode slice 10k", |b| {
+        let mut buffer = String::new();
+        b.iter(|| {
+            buffer.clear();
+            crockford::encode_slice(black_box(&values), ',', &mut buffer);
+        })
+    });
 }
 
 criterion_group!(encode, encode_benchmark);
@@ -0,0 +1,48 @@
+use std::{
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use crockford::{Cuid2Generator, Scru128Generator, Uuid7Generator};
+
+const THREADS: usize = 4;
+
+/// Times `iters` calls on each of several threads at once, so each iteration reports the cost of
+/// one ID while the other threads are competing for the generator.
+fn contended<T, F: Fn() -> T + Sync>(iters: u64, f: F) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| (0..iters).for_each(|_| drop(black_box(f()))));
+        }
+    });
+    start.elapsed()
+}
+
+fn generate_benchmark(c: &mut Criterion) {
+    c.bench_function("uuid7 generator", |b| {
+        let generator = Uuid7Generator::new();
+        b.iter(|| black_box(generator.generate()))
+    });
+
+    c.bench_function("uuid7 generator contended", |b| {
+        let generator = Uuid7Generator::new();
+        b.iter_custom(|iters| contended(iters, || generator.generate()))
+    });
+
+    c.bench_function("cuid2 generator contended", |b| {
+        let generator = Cuid2Generator::new();
+        b.iter_custom(|iters| contended(iters, || generator.generate()))
+    });
+
+    c.bench_function("scru128 generator in mutex contended", |b| {
+        let generator = Mutex::new(Scru128Generator::with_rng(rand::rngs::OsRng));
+        b.iter_custom(|iters| contended(iters, || generator.lock().unwrap().generate()))
+    });
+}
+
+criterion_group!(generate, generate_benchmark);
+
+criterion_main!(generate);
@@ -0,0 +1,125 @@
+use core::{convert::TryFrom, ops::RangeInclusive};
+
+use alloc::string::String;
+
+use crate::{
+    decoding::decode_padded, encoding::encode_padded, error::Kind, Error, Result,
+    MAX_ENCODED_LEN_U64,
+};
+
+/// Returns the shortest prefix of a value's full thirteen-digit encoding that is not ambiguous.
+///
+/// Much like abbreviated commit hashes in git, prefixes are tried from `min_len` digits upward
+/// and the first one rejected by `is_ambiguous` is returned. The caller decides what counts as
+/// ambiguous, usually by checking whether any other known value shares the prefix. If every
+/// shorter prefix is ambiguous, the full encoding is returned.
+///
+/// Prefixes are taken from the zero-padded encoding, so that each one stands for a single range
+/// of values, as `decode_prefix` returns. This suits values spread over the whole `u64` range,
+/// such as hashes or random identifiers. Small values all begin with a run of zeros and gain
+/// nothing from abbreviation; `encode` already writes them without the padding.
+///
+/// ```rust
+/// let known = [0xF000_0000_0000_0000, 0xF800_0000_0000_0000];
+///
+/// let abbrev = crockford::abbreviate(known[0], 1, |prefix| {
+///     known
+///         .iter()
+///         .filter(|&&n| crockford::decode_prefix(prefix).unwrap().contains(&n))
+///         .count()
+///         > 1
+/// });
+///
+/// assert_eq!("F0", abbrev);
+/// ```
+pub fn abbreviate<F>(n: u64, min_len: usize, mut is_ambiguous: F) -> String
+where
+    F: FnMut(&str) -> bool,
+{
+    let mut full = String::with_capacity(MAX_ENCODED_LEN_U64);
+    encode_padded(n.into(), MAX_ENCODED_LEN_U64, &mut full);
+
+    let min_len = min_len.clamp(1, MAX_ENCODED_LEN_U64);
+    for len in min_len..MAX_ENCODED_LEN_U64 {
+        if !is_ambiguous(&full[..len]) {
+            full.truncate(len);
+            break;
+        }
+    }
+
+    full
+}
+
+/// Attempts to decode an abbreviation into the range of values whose full encodings begin with it.
+pub fn decode_prefix<T: AsRef<str>>(prefix: T) -> Result<RangeInclusive<u64>> {
+    let prefix = prefix.as_ref();
+    match prefix.len() {
+        0 => Err(Error::new(Kind::EmptyString, "Encoded prefix is empty.")),
+
+        n if n > MAX_ENCODED_LEN_U64 => {
+            Err(Error::new(Kind::OutOfRange, "Encoded prefix is too long"))
+        }
+
+        n => {
+            let free = 5 * (MAX_ENCODED_LEN_U64 - n) as u32;
+            let start = decode_padded(prefix, n)? << free;
+            let start = u64::try_from(start)
+                .map_err(|_| Error::new(Kind::OutOfRange, "Encoded prefix is too large"))?;
+            let end = start | u64::MAX.checked_shr(64 - free).unwrap_or(0);
+
+            Ok(start..=end)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{abbreviate, decode_prefix, error::Kind, Error};
+
+    #[test]
+    fn unambiguous_values_use_min_len() {
+        assert_eq!("0000", abbreviate(5111, 4, |_| false));
+    }
+
+    #[test]
+    fn ambiguous_prefixes_are_extended() {
+        let abbrev = abbreviate(5111, 1, |prefix| prefix.len() < 6);
+        assert_eq!("000000", abbrev);
+    }
+
+    #[test]
+    fn small_values_abbreviate_from_the_padded_encoding() {
+        let known = [5111, 5112];
+        let abbrev = abbreviate(known[0], 1, |prefix| {
+            let range = decode_prefix(prefix).unwrap();
+            known.iter().filter(|&n| range.contains(n)).count() > 1
+        });
+
+        assert_eq!("00000000004ZQ", abbrev);
+        assert_eq!(Ok(5111..=5111), decode_prefix(&abbrev));
+    }
+
+    #[test]
+    fn fully_ambiguous_values_are_not_abbreviated() {
+        assert_eq!("00000000004ZQ", abbreviate(5111, 1, |_| true));
+    }
+
+    #[test]
+    fn prefixes_decode_to_ranges() {
+        assert_eq!(Ok(0..=u64::MAX >> 4), decode_prefix("0"));
+        assert_eq!(Ok(5111..=5111), decode_prefix("00000000004zq"));
+        assert_eq!(Ok(0xF000_0000_0000_0000..=u64::MAX), decode_prefix("F"));
+    }
+
+    #[test]
+    fn oversized_prefixes_fail() {
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+        assert_eq!(expected, decode_prefix("G"));
+    }
+
+    #[test]
+    fn empty_prefixes_fail() {
+        let expected = Err(Error::new(Kind::EmptyString, "Don't care"));
+        assert_eq!(expected, decode_prefix(""));
+    }
+}
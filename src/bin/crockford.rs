@@ -0,0 +1,419 @@
+use std::{
+    env,
+    fmt::Write as _,
+    io::{self, BufRead, Write},
+    process,
+};
+
+use crockford::{Explanation, Reading};
+
+const USAGE: &str = "\
+usage: crockford [options] [value...]
+       crockford explain [code...]
+
+Encodes each value, or decodes it with --decode. With no values, reads whitespace-separated
+values from standard input. The explain command instead prints how each code is read, symbol
+by symbol, along with its check symbol math.
+
+options:
+    -d, --decode     decode encoded strings into numbers
+    -c, --check      append a check symbol when encoding, or verify and strip it when decoding
+    -g, --group N    separate every N digits of the output with a hyphen
+    -l, --lower      write encoded output in lowercase
+    -u, --upper      write encoded output in uppercase (the default)
+    -h, --help       print this message";
+
+fn main() {
+    let (options, values) = match Options::from_args(env::args().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("crockford: {}\n\n{}", message, USAGE);
+            process::exit(2);
+        }
+    };
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let out = io::BufWriter::new(stdout.lock());
+
+    let code = match run(&options, &values, stdin.lock(), out) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        // Whatever was reading the output has gone away, as with `crockford ... | head`.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => 0,
+        Err(e) => {
+            eprintln!("crockford: {}", e);
+            1
+        }
+    };
+    process::exit(code);
+}
+
+/// Converts each value, or each whitespace-separated value read from `input` if there are none.
+///
+/// Returns whether every value converted; conversion errors are reported on standard error as
+/// they happen. I/O errors stop the run.
+fn run<R: BufRead, W: Write>(
+    options: &Options,
+    values: &[String],
+    input: R,
+    mut out: W,
+) -> io::Result<bool> {
+    let mut succeeded = true;
+    let mut convert = |value: &str| -> io::Result<()> {
+        match options.convert(value) {
+            Ok(converted) => writeln!(out, "{}", converted)?,
+            Err(message) => {
+                eprintln!("crockford: {}: {}", value, message);
+                succeeded = false;
+            }
+        }
+        Ok(())
+    };
+
+    if values.is_empty() {
+        for line in input.lines() {
+            line?.split_whitespace().try_for_each(&mut convert)?;
+        }
+    } else {
+        values
+            .iter()
+            .map(String::as_str)
+            .try_for_each(&mut convert)?;
+    }
+
+    out.flush()?;
+    Ok(succeeded)
+}
+
+#[derive(Default)]
+struct Options {
+    explain: bool,
+    decode: bool,
+    check: bool,
+    group: Option<usize>,
+    lower: bool,
+}
+
+impl Options {
+    fn from_args<I: Iterator<Item = String>>(args: I) -> Result<(Options, Vec<String>), String> {
+        let mut options = Options::default();
+        let mut values = Vec::new();
+        let mut args = args.peekable();
+
+        if args.peek().map(String::as_str) == Some("explain") {
+            args.next();
+            options.explain = true;
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-d" | "--decode" => options.decode = true,
+                "-c" | "--check" => options.check = true,
+                "-l" | "--lower" => options.lower = true,
+                "-u" | "--upper" => options.lower = false,
+                "-g" | "--group" => {
+                    let size = args.next().ok_or("--group needs a group size")?;
+                    match size.parse() {
+                        Ok(0) | Err(_) => return Err(format!("invalid group size: {}", size)),
+                        Ok(size) => options.group = Some(size),
+                    }
+                }
+                "-h" | "--help" => {
+                    println!("{}", USAGE);
+                    process::exit(0);
+                }
+                "--" => values.extend(&mut args),
+                other if other.starts_with('-') && other.len() > 1 => {
+                    return Err(format!("unknown option: {}", other))
+                }
+                _ => values.push(arg),
+            }
+        }
+
+        Ok((options, values))
+    }
+
+    fn convert(&self, value: &str) -> Result<String, String> {
+        if self.explain {
+            return Ok(render_explanation(&crockford::explain(value)));
+        }
+
+        if self.decode {
+            let n = if self.check {
+                crockford::decode_with_check(value.replace('-', ""))
+            } else {
+                crockford::decode_grouped(value, '-')
+            };
+            return n.map(|n| n.to_string()).map_err(|e| e.to_string());
+        }
+
+        let n: u64 = value.parse().map_err(|_| "not a u64".to_owned())?;
+        let encoded = match (self.check, self.group) {
+            (false, Some(size)) => crockford::encode_grouped(n, size, '-'),
+            (false, None) => crockford::encode(n),
+            (true, group) => {
+                let encoded = crockford::encode_with_check(n);
+                match group {
+                    Some(size) => crockford::group_encoded(&encoded, size, '-'),
+                    None => encoded,
+                }
+            }
+        };
+
+        Ok(if self.lower {
+            encoded.to_ascii_lowercase()
+        } else {
+            encoded
+        })
+    }
+}
+
+/// Lays out an explanation as a table of symbols followed by the value and check symbol math.
+fn render_explanation(explanation: &Explanation) -> String {
+    let mut out = String::new();
+    row(&mut out, "index", "symbol", "value", "bits", "accumulated");
+
+    for symbol in &explanation.symbols {
+        let index = symbol.index.to_string();
+        let found = symbol.found.to_string();
+
+        match symbol.reading {
+            Reading::Digit {
+                value,
+                canonical,
+                shift,
+                accumulated,
+            } => {
+                let found = if symbol.found == char::from(canonical) {
+                    found
+                } else {
+                    format!("{} ({})", found, char::from(canonical))
+                };
+                let accumulated = accumulated.map_or("overflow".to_owned(), |n| n.to_string());
+                let bits = format!("{}-{}", shift, shift + 4);
+                row(
+                    &mut out,
+                    &index,
+                    &found,
+                    &value.to_string(),
+                    &bits,
+                    &accumulated,
+                );
+            }
+            Reading::CheckOnly { value } => row(
+                &mut out,
+                &index,
+                &found,
+                &value.to_string(),
+                "",
+                "check symbol only",
+            ),
+            Reading::Separator => row(&mut out, &index, &found, "", "", "ignored"),
+            Reading::Invalid => row(&mut out, &index, &found, "", "", "invalid"),
+        }
+    }
+
+    out.push('\n');
+    match explanation.value {
+        Some(value) => write!(out, "value: {}", value),
+        None => write!(out, "value: none (invalid or too large)"),
+    }
+    .unwrap();
+
+    if explanation.hyphenated {
+        out.push_str("\nhyphens: skipped here, but only lenient decoding accepts them");
+    }
+
+    if let Some(check) = explanation.check {
+        write!(
+            out,
+            "\nas check symbol: found {}, digits before it are {} mod 37, expecting {}: {}",
+            char::from(check.found),
+            check.remainder,
+            char::from(check.expected),
+            if check.matches { "ok" } else { "mismatch" }
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+fn row(out: &mut String, index: &str, symbol: &str, value: &str, bits: &str, rest: &str) {
+    writeln!(
+        out,
+        "{:<6}{:<8}{:<7}{:<7}{}",
+        index, symbol, value, bits, rest
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+
+    use super::Options;
+
+    fn parse(args: &[&str]) -> Result<(Options, Vec<String>), String> {
+        Options::from_args(args.iter().map(|&arg| arg.to_owned()))
+    }
+
+    fn convert(args: &[&str], value: &str) -> Result<String, String> {
+        parse(args).unwrap().0.convert(value)
+    }
+
+    fn run(args: &[&str], input: &str) -> (io::Result<bool>, String) {
+        let (options, values) = parse(args).unwrap();
+        let mut out = Vec::new();
+        let result = super::run(&options, &values, input.as_bytes(), &mut out);
+        (result, String::from_utf8(out).unwrap())
+    }
+
+    /// A writer whose reader has gone away.
+    struct ClosedPipe;
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn values_are_read_from_input_when_none_are_given() {
+        let (result, output) = run(&[], "5111 0\n36\n");
+
+        assert!(result.unwrap());
+        assert_eq!("4ZQ\n0\n14\n", output);
+    }
+
+    #[test]
+    fn conversion_errors_do_not_stop_the_run() {
+        let (result, output) = run(&["-d", "4ZQ", "!", "14"], "");
+
+        assert!(!result.unwrap());
+        assert_eq!("5111\n36\n", output);
+    }
+
+    #[test]
+    fn write_errors_stop_the_run() {
+        let (options, values) = parse(&["5111"]).unwrap();
+        let error = super::run(&options, &values, io::empty(), ClosedPipe).unwrap_err();
+
+        assert_eq!(io::ErrorKind::BrokenPipe, error.kind());
+    }
+
+    #[test]
+    fn options_and_values_are_separated() {
+        let (options, values) = parse(&["-d", "4ZQ", "--check", "5"]).unwrap();
+
+        assert!(options.decode);
+        assert!(options.check);
+        assert_eq!(vec!["4ZQ", "5"], values);
+    }
+
+    #[test]
+    fn later_case_options_win() {
+        assert!(parse(&["-u", "--lower"]).unwrap().0.lower);
+        assert!(!parse(&["-l", "--upper"]).unwrap().0.lower);
+    }
+
+    #[test]
+    fn double_dash_ends_options() {
+        let (options, values) = parse(&["--", "-d", "-"]).unwrap();
+
+        assert!(!options.decode);
+        assert_eq!(vec!["-d", "-"], values);
+    }
+
+    #[test]
+    fn group_sizes_are_validated() {
+        assert_eq!(Some(4), parse(&["-g", "4"]).unwrap().0.group);
+        assert!(parse(&["--group"]).is_err());
+        assert!(parse(&["--group", "0"]).is_err());
+        assert!(parse(&["--group", "x"]).is_err());
+    }
+
+    #[test]
+    fn unknown_options_are_rejected() {
+        assert!(parse(&["--frobnicate"]).is_err());
+        assert!(parse(&["-x"]).is_err());
+    }
+
+    #[test]
+    fn values_are_encoded() {
+        assert_eq!(Ok("4ZQ".to_owned()), convert(&[], "5111"));
+        assert_eq!(Ok("4zq".to_owned()), convert(&["-l"], "5111"));
+        assert_eq!(Ok("4ZQ5".to_owned()), convert(&["-c"], "5111"));
+        assert_eq!(Ok("4Z-Q".to_owned()), convert(&["-g", "2"], "5111"));
+        assert_eq!(
+            Ok("4z-q5".to_owned()),
+            convert(&["-c", "-g", "2", "-l"], "5111")
+        );
+        assert!(convert(&[], "4ZQ").is_err());
+    }
+
+    #[test]
+    fn values_are_decoded() {
+        assert_eq!(Ok("5111".to_owned()), convert(&["-d"], "4zq"));
+        assert_eq!(Ok("5111".to_owned()), convert(&["-d"], "4Z-Q"));
+        assert_eq!(Ok("5111".to_owned()), convert(&["-d", "-c"], "4Z-Q5"));
+        assert!(convert(&["-d", "-c"], "4ZQ6").is_err());
+        assert!(convert(&["-d"], "4Z!").is_err());
+    }
+
+    #[test]
+    fn explain_is_a_leading_command() {
+        let (options, values) = parse(&["explain", "4ZQ-X"]).unwrap();
+        assert!(options.explain);
+        assert_eq!(vec!["4ZQ-X"], values);
+
+        let (options, values) = parse(&["4ZQ", "explain"]).unwrap();
+        assert!(!options.explain);
+        assert_eq!(vec!["4ZQ", "explain"], values);
+    }
+
+    #[test]
+    fn explanations_list_every_symbol() {
+        let explained = convert(&["explain"], "4zQ5").unwrap();
+        let lines: Vec<_> = explained.lines().collect();
+
+        assert_eq!(
+            vec![
+                "index symbol  value  bits   accumulated",
+                "0     4       4      15-19  4",
+                "1     z (Z)   31     10-14  159",
+                "2     Q       23     5-9    5111",
+                "3     5       5      0-4    163557",
+                "",
+                "value: 163557",
+                "as check symbol: found 5, digits before it are 5 mod 37, expecting 5: ok",
+            ],
+            lines
+        );
+    }
+
+    #[test]
+    fn explanations_flag_hyphens() {
+        assert!(convert(&["explain"], "4ZQ-X").unwrap().contains("hyphens"));
+        assert!(!convert(&["explain"], "4ZQX").unwrap().contains("hyphens"));
+    }
+
+    #[test]
+    fn explanations_never_fail() {
+        let explained = convert(&["explain"], "4ZQé").unwrap();
+
+        assert!(explained.contains("invalid"));
+        assert!(explained.contains("value: none"));
+    }
+
+    #[test]
+    fn non_ascii_values_fail_to_decode() {
+        assert!(convert(&["-d"], "4ZQé").is_err());
+        assert!(convert(&["-d", "-c"], "4ZQé").is_err());
+    }
+}
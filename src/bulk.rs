@@ -0,0 +1,428 @@
+use std::{
+    cmp,
+    io::{self, BufRead, BufReader, Read},
+    num::NonZeroUsize,
+    thread,
+};
+
+#[cfg(feature = "mmap")]
+use std::{fs::File, path::Path};
+
+use crate::{decoding::decode_raw, encode_into, error::LineError, Error, MAX_ENCODED_LEN_U64};
+
+/// How much encoded output to gather before handing it to the writer.
+const WRITE_BATCH_LEN: usize = 1 << 16;
+
+/// Inputs shorter than this are not worth splitting across threads.
+const MIN_CHUNK_LEN: usize = 1 << 16;
+
+/// Decodes every whitespace-separated value in the input, in order.
+///
+/// Large inputs are split at line breaks and decoded on several threads at once. Decoding stops
+/// at the first invalid value, and the error says where in the input it is.
+///
+/// ```rust
+/// let values = crockford::decode_all(b"4ZQ 1\n\nfzzzzzzzzzzzz\n").unwrap();
+/// assert_eq!(vec![5111, 1, u64::MAX], values);
+///
+/// let error = crockford::decode_all(b"4ZQ\n 4!").unwrap_err();
+/// assert_eq!((2, 3), (error.line(), error.column()));
+/// ```
+pub fn decode_all(input: &[u8]) -> Result<Vec<u64>, LineError> {
+    let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let chunks = split_lines(input, threads);
+
+    if chunks.len() == 1 {
+        return decode_chunk(input).map(|(values, _)| values);
+    }
+
+    let results: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = chunks
+            .iter()
+            .map(|&chunk| scope.spawn(move || decode_chunk(chunk)))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("decoding does not panic"))
+            .collect()
+    });
+
+    let mut values = Vec::new();
+    let mut lines = 0;
+    for result in results {
+        let (chunk_values, chunk_lines) = result.map_err(|e| e.offset_lines(lines))?;
+        values.extend(chunk_values);
+        lines += chunk_lines;
+    }
+
+    Ok(values)
+}
+
+/// Memory-maps a file and decodes every whitespace-separated value in it, in order.
+///
+/// Returns an `InvalidData` error wrapping a [`LineError`] if the file contains an invalid value.
+/// See [`decode_all`] for details.
+#[cfg(feature = "mmap")]
+pub fn decode_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<u64>> {
+    let file = File::open(path)?;
+
+    // Safety: the map is only read while we hold it, but another process truncating or rewriting
+    // the file in the meantime can still corrupt what we see. Ingest jobs are expected to own
+    // their input files.
+    let map = unsafe { memmap2::Mmap::map(&file)? };
+
+    decode_all(&map).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Decodes one value per line from a reader.
+///
+/// Whitespace around each value, including the carriage return of a Windows line ending, is
+/// ignored, as are blank lines. Errors carry the line they happened on, and iteration carries on
+/// with the next line afterward.
+///
+/// ```rust
+/// let input = "4ZQ\r\n\n  fzzzzzzzzzzzz\n4!\n1\n";
+/// let mut values = crockford::decode_lines(input.as_bytes());
+///
+/// assert_eq!(5111, values.next().unwrap().unwrap());
+/// assert_eq!(u64::MAX, values.next().unwrap().unwrap());
+/// assert_eq!(4, values.next().unwrap().unwrap_err().line());
+/// assert_eq!(1, values.next().unwrap().unwrap());
+/// assert!(values.next().is_none());
+/// ```
+pub fn decode_lines<R: BufRead>(r: R) -> impl Iterator<Item = Result<u64, LineError>> {
+    decode_split(r, b'\n')
+}
+
+/// Decodes separator-delimited values from a reader.
+///
+/// The reader is buffered internally, so there is no need to wrap it in a `BufReader`. As with
+/// `decode_lines`, whitespace around each value is ignored and so are empty values, which makes a
+/// trailing separator harmless. Errors report which value they happened in as their line, so with
+/// a separator of `b'\n'` this reads exactly like `decode_lines`.
+///
+/// ```rust
+/// let input = "4ZQ, 1,\nfzzzzzzzzzzzz,";
+/// let values: Vec<_> = crockford::decode_stream(input.as_bytes(), b',')
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+///
+/// assert_eq!(vec![5111, 1, u64::MAX], values);
+/// ```
+pub fn decode_stream<R: Read>(r: R, sep: u8) -> impl Iterator<Item = Result<u64, LineError>> {
+    decode_split(BufReader::with_capacity(WRITE_BATCH_LEN, r), sep)
+}
+
+fn decode_split<R: BufRead>(r: R, sep: u8) -> impl Iterator<Item = Result<u64, LineError>> {
+    r.split(sep).zip(1..).filter_map(|(line, number)| {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => return Some(Err(LineError::io(number, e))),
+        };
+
+        let start = line.iter().position(|u| !u.is_ascii_whitespace())?;
+        let end = line
+            .iter()
+            .rposition(|u| !u.is_ascii_whitespace())
+            .map_or(line.len(), |idx| idx + 1);
+
+        Some(decode_raw(&line[start..end]).map_err(|e| locate(number, start, e)))
+    })
+}
+
+/// Encodes each value on its own line and writes them all to the provided writer.
+///
+/// Output is gathered into large batches in a single reused buffer, so there is no need to wrap
+/// the writer in a `BufWriter`. The writer is flushed once everything is written.
+///
+/// ```rust
+/// let mut output = Vec::new();
+/// crockford::write_lines(&[5111, 1, u64::MAX], &mut output).unwrap();
+///
+/// assert_eq!(b"4ZQ\n1\nFZZZZZZZZZZZZ\n", &output[..]);
+/// ```
+pub fn write_lines<W: io::Write>(values: &[u64], w: W) -> io::Result<()> {
+    encode_stream(values.iter().copied(), b'\n', w)
+}
+
+/// Encodes each value followed by the provided separator and writes them all to the provided
+/// writer.
+///
+/// This is `write_lines` for any iterator and any separator, and batches its output the same way.
+/// `decode_stream` reads the result back.
+///
+/// ```rust
+/// let mut output = Vec::new();
+/// crockford::encode_stream((0..3).map(|n| n * 5111), b',', &mut output).unwrap();
+///
+/// assert_eq!(b"0,4ZQ,9ZE,", &output[..]);
+/// ```
+pub fn encode_stream<I, W>(values: I, sep: u8, mut w: W) -> io::Result<()>
+where
+    I: IntoIterator<Item = u64>,
+    W: io::Write,
+{
+    let mut buf = Vec::with_capacity(WRITE_BATCH_LEN);
+
+    for n in values {
+        if buf.len() + MAX_ENCODED_LEN_U64 + 1 > WRITE_BATCH_LEN {
+            w.write_all(&buf)?;
+            buf.clear();
+        }
+        encode_into(n, &mut buf);
+        buf.push(sep);
+    }
+
+    w.write_all(&buf)?;
+    w.flush()
+}
+
+/// Splits the input into roughly `parts` chunks, each ending just after a line break.
+fn split_lines(input: &[u8], parts: usize) -> Vec<&[u8]> {
+    let target = cmp::max(input.len() / parts, MIN_CHUNK_LEN);
+    let mut chunks = Vec::with_capacity(parts);
+    let mut rest = input;
+
+    while rest.len() > target {
+        match rest[target..].iter().position(|&u| u == b'\n') {
+            Some(idx) => {
+                let (chunk, tail) = rest.split_at(target + idx + 1);
+                chunks.push(chunk);
+                rest = tail;
+            }
+            None => break,
+        }
+    }
+
+    chunks.push(rest);
+    chunks
+}
+
+/// Decodes a chunk, returning its values and the number of line breaks in it.
+fn decode_chunk(chunk: &[u8]) -> Result<(Vec<u64>, usize), LineError> {
+    let mut values = Vec::new();
+    let mut line = 1;
+    let mut line_start = 0;
+    let mut idx = 0;
+
+    while idx < chunk.len() {
+        match chunk[idx] {
+            b'\n' => {
+                line += 1;
+                line_start = idx + 1;
+                idx += 1;
+            }
+            u if u.is_ascii_whitespace() => idx += 1,
+            _ => {
+                let start = idx;
+                while idx < chunk.len() && !chunk[idx].is_ascii_whitespace() {
+                    idx += 1;
+                }
+
+                match decode_raw(&chunk[start..idx]) {
+                    Ok(n) => values.push(n),
+                    Err(e) => return Err(locate(line, start - line_start, e)),
+                }
+            }
+        }
+    }
+
+    Ok((values, line - 1))
+}
+
+/// Attaches a location to an error in a value starting `offset` bytes into its line.
+fn locate(line: usize, offset: usize, e: Error) -> LineError {
+    let column = offset + 1 + e.index().unwrap_or(0);
+    LineError::new(line, column, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufReader, Read};
+
+    use super::{split_lines, MIN_CHUNK_LEN};
+    use crate::{
+        decode_all, decode_lines, decode_stream, encode, encode_stream, error::Kind, write_lines,
+        Error, LineError,
+    };
+
+    #[test]
+    fn values_decode_in_order() {
+        assert_eq!(Ok(vec![]), decode_all(b""));
+        assert_eq!(Ok(vec![]), decode_all(b" \r\n\t\n"));
+        assert_eq!(Ok(vec![1, 2, 3]), decode_all(b"1 2\r\n\t3"));
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_byte() {
+        assert_eq!(
+            Err(LineError::new(
+                3,
+                4,
+                Error::new(Kind::InvalidDigit(0, 0), "Don't care")
+            )),
+            decode_all(b"1\n2\n  4! 5")
+        );
+        assert_eq!(
+            Err(LineError::new(
+                1,
+                3,
+                Error::new(Kind::OutOfRange, "Don't care")
+            )),
+            decode_all(b"1 ZZZZZZZZZZZZZ")
+        );
+    }
+
+    #[test]
+    fn chunks_end_at_line_breaks() {
+        let input = vec![b'\n'; MIN_CHUNK_LEN * 3 + 1];
+        let chunks = split_lines(&input, 3);
+
+        assert_eq!(3, chunks.len());
+        assert!(chunks[..2].iter().all(|chunk| chunk.ends_with(b"\n")));
+        assert_eq!(
+            input.len(),
+            chunks.iter().map(|chunk| chunk.len()).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn large_inputs_decode_across_chunks() {
+        let mut input = String::new();
+        for n in 0..100_000u64 {
+            input.push_str(&encode(n * 7919));
+            input.push('\n');
+        }
+
+        let values = decode_all(input.as_bytes()).unwrap();
+        assert_eq!(100_000, values.len());
+        assert!(values.iter().zip(0..).all(|(&v, n)| v == n * 7919));
+
+        input.push_str("0\n7UUU\n");
+        let error = decode_all(input.as_bytes()).unwrap_err();
+        assert_eq!((100_002, 2), (error.line(), error.column()));
+    }
+
+    #[test]
+    fn lines_decode_with_locations() {
+        let input: &[u8] = b"1\n\t4!\n\n 2 \r\n3 4\n";
+        let results: Vec<_> = decode_lines(input).collect();
+        let invalid_digit = Error::new(Kind::InvalidDigit(0, 0), "Don't care");
+
+        assert_eq!(
+            vec![
+                Ok(1),
+                Err(LineError::new(2, 3, invalid_digit)),
+                Ok(2),
+                Err(LineError::new(
+                    5,
+                    2,
+                    Error::new(Kind::InvalidDigit(0, 0), "Don't care")
+                )),
+            ],
+            results
+        );
+    }
+
+    #[test]
+    fn io_errors_carry_the_line() {
+        struct Broken;
+
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"))
+            }
+        }
+
+        let reader = io::Cursor::new(b"1\n".to_vec()).chain(Broken);
+        let mut values = decode_lines(BufReader::new(reader));
+
+        assert_eq!(Some(Ok(1)), values.next());
+
+        let error = values.next().unwrap().unwrap_err();
+        assert_eq!(2, error.line());
+        assert!(error.error().is_none());
+        assert_eq!(io::ErrorKind::BrokenPipe, error.io_error().unwrap().kind());
+    }
+
+    #[test]
+    fn written_lines_decode_back() {
+        let values: Vec<_> = (0..20_000u64)
+            .map(|n| n.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .collect();
+        let mut output = Vec::new();
+        write_lines(&values, &mut output).unwrap();
+
+        assert_eq!(Ok(values), decode_all(&output));
+    }
+
+    #[test]
+    fn writes_are_batched() {
+        struct Counting(usize);
+
+        impl io::Write for Counting {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0 += 1;
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut counting = Counting(0);
+        write_lines(&[u64::MAX; 20_000], &mut counting).unwrap();
+
+        // 280,000 bytes of output in batches of up to 64 KiB.
+        assert_eq!(5, counting.0);
+    }
+
+    #[test]
+    fn streams_round_trip_with_any_separator() {
+        let values: Vec<_> = (0..20_000u64).map(|n| n * 7919).collect();
+
+        for &sep in b"\n,; " {
+            let mut output = Vec::new();
+            encode_stream(values.iter().copied(), sep, &mut output).unwrap();
+
+            let decoded: Result<Vec<_>, _> = decode_stream(&output[..], sep).collect();
+            assert_eq!(Ok(values.clone()), decoded);
+        }
+    }
+
+    #[test]
+    fn stream_errors_count_values() {
+        let results: Vec<_> = decode_stream(&b"1,,4!, 2"[..], b',').collect();
+
+        assert_eq!(
+            vec![
+                Ok(1),
+                Err(LineError::new(
+                    3,
+                    2,
+                    Error::new(Kind::InvalidDigit(0, 0), "Don't care")
+                )),
+                Ok(2),
+            ],
+            results
+        );
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn files_decode() {
+        use std::{fs, io};
+
+        let path = std::env::temp_dir().join(format!("crockford-bulk-{}", std::process::id()));
+        fs::write(&path, "4ZQ\nfzzzzzzzzzzzz\n").unwrap();
+        assert_eq!(vec![5111, u64::MAX], crate::decode_file(&path).unwrap());
+
+        fs::write(&path, "4ZQ\n4U\n").unwrap();
+        let error = crate::decode_file(&path).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
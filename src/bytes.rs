@@ -0,0 +1,192 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+#[cfg(feature = "alloc")]
+use crate::{decoding::to_normal_digit, error::Kind, Error, Result};
+use crate::{Write, UPPERCASE_ENCODING};
+
+#[cfg(feature = "alloc")]
+/// Encodes arbitrary bytes as a Crockford Base32-encoded string.
+///
+/// Every five bits of input, most significant first, become one digit; if the input does not
+/// divide evenly into five-bit groups, the last digit is padded with zero bits. There is no
+/// padding character. Unlike `encode`, leading zero bytes are kept, so a 16-byte UUID always
+/// encodes to 26 digits.
+///
+/// ```rust
+/// assert_eq!("CR", crockford::encode_bytes(b"f"));
+/// assert_eq!("CSQPYRK1E8", crockford::encode_bytes(b"foobar"));
+/// assert_eq!("", crockford::encode_bytes(b""));
+/// ```
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(encoded_bytes_len(bytes.len()));
+    encode_bytes_into(bytes, &mut s);
+    s
+}
+
+/// Encodes arbitrary bytes as Crockford Base32 and writes them to the provided output.
+///
+/// This is the byte counterpart of `encode_into`; see `encode_bytes` for the format.
+pub fn encode_bytes_into<T: Write>(bytes: &[u8], w: &mut T) {
+    let mut acc: u16 = 0;
+    let mut bits = 0;
+
+    for &u in bytes {
+        acc = acc << 8 | u16::from(u);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            w.write(UPPERCASE_ENCODING[usize::from(acc >> bits & 0x1F)]);
+        }
+        acc &= (1 << bits) - 1;
+    }
+
+    if bits > 0 {
+        w.write(UPPERCASE_ENCODING[usize::from(acc << (5 - bits) & 0x1F)]);
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Attempts to decode a Crockford Base32-encoded string into the bytes it represents.
+///
+/// This is the inverse of `encode_bytes`. Digits are read case-insensitively, with the usual
+/// folding of ambiguous symbols. Inputs whose length could not have come from `encode_bytes`, or
+/// whose padding bits are not zero, are rejected so that every byte string has exactly one
+/// encoding.
+///
+/// ```rust
+/// # use crockford::Error;
+/// # fn run() -> Result<(), Error> {
+/// assert_eq!(b"foobar".to_vec(), crockford::decode_bytes("csqpyrk1e8")?);
+/// assert!(crockford::decode_bytes("CS").is_err());
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+pub fn decode_bytes<T: AsRef<str>>(input: T) -> Result<Vec<u8>> {
+    let input = input.as_ref();
+    let mut bytes = Vec::with_capacity(input.len() * 5 / 8);
+    decode_bytes_into(input, &mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(feature = "alloc")]
+/// Attempts to decode a Crockford Base32-encoded string, appending the bytes to the provided
+/// buffer.
+///
+/// On failure, the buffer may have had some bytes appended to it. See `decode_bytes` for the
+/// format.
+pub fn decode_bytes_into<T: AsRef<str>>(input: T, bytes: &mut Vec<u8>) -> Result<()> {
+    let input = input.as_ref();
+
+    // Only these remainders can be left by a whole number of bytes.
+    if let 1 | 3 | 6 = input.len() % 8 {
+        return Err(Error::new(
+            Kind::InvalidLength,
+            "Encoded input has the wrong length.",
+        ));
+    }
+
+    bytes.reserve(input.len() * 5 / 8);
+
+    let mut acc: u16 = 0;
+    let mut bits = 0;
+
+    for (idx, u) in input.bytes().enumerate() {
+        acc = acc << 5 | u16::from(to_normal_digit(idx, u)?);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+        }
+        acc &= (1 << bits) - 1;
+    }
+
+    if acc != 0 {
+        return Err(Error::new(
+            Kind::InvalidFormat,
+            "Encoded input has nonzero padding bits.",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Returns the number of digits produced by encoding the provided number of bytes.
+///
+/// This is useful for sizing a buffer before calling `encode_bytes_into`.
+///
+/// ```rust
+/// assert_eq!(10, crockford::encoded_bytes_len(b"foobar".len()));
+/// assert_eq!(26, crockford::encoded_bytes_len(16));
+/// ```
+pub const fn encoded_bytes_len(len: usize) -> usize {
+    (len * 8).div_ceil(5)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        decode_bytes, decode_bytes_into, encode_bytes, encoded_bytes_len, error::Kind, Error,
+    };
+
+    #[test]
+    fn known_values_encode() {
+        assert_eq!("ZW", encode_bytes(&[0xFF]));
+        assert_eq!("00", encode_bytes(&[0]));
+        assert_eq!("00000000", encode_bytes(&[0; 5]));
+        assert_eq!("ZZZZZZZZ", encode_bytes(&[0xFF; 5]));
+        assert_eq!(
+            "041061050R3GG28A",
+            encode_bytes(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10])
+        );
+    }
+
+    #[test]
+    fn every_length_round_trips() {
+        let input: Vec<u8> = (0..=255).collect();
+
+        for len in 0..input.len() {
+            let encoded = encode_bytes(&input[..len]);
+            assert_eq!(encoded_bytes_len(len), encoded.len());
+            assert_eq!(Ok(input[..len].to_vec()), decode_bytes(&encoded), "{}", len);
+        }
+    }
+
+    #[test]
+    fn uuids_take_26_digits() {
+        assert_eq!(26, encode_bytes(&[0xAB; 16]).len());
+    }
+
+    #[test]
+    fn impossible_lengths_fail() {
+        let expected = Err(Error::new(Kind::InvalidLength, "Don't care"));
+
+        assert_eq!(expected, decode_bytes("0"));
+        assert_eq!(expected, decode_bytes("000"));
+        assert_eq!(expected, decode_bytes("000000"));
+        assert_eq!(expected, decode_bytes("000000000"));
+    }
+
+    #[test]
+    fn nonzero_padding_fails() {
+        let expected = Err(Error::new(Kind::InvalidFormat, "Don't care"));
+
+        assert_eq!(expected, decode_bytes("ZZ"));
+        assert_eq!(Ok(vec![0xFF]), decode_bytes("zw"));
+    }
+
+    #[test]
+    fn invalid_digits_fail() {
+        let expected = Err(Error::new(Kind::InvalidDigit(0, 0), "Don't care"));
+        assert_eq!(expected, decode_bytes("Z!"));
+    }
+
+    #[test]
+    fn decoding_appends_to_the_buffer() {
+        let mut bytes = vec![1];
+        decode_bytes_into("ZW", &mut bytes).unwrap();
+
+        assert_eq!(vec![1, 0xFF], bytes);
+    }
+}
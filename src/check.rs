@@ -0,0 +1,183 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "alloc")]
+use crate::UPPERCASE_ENCODING;
+use crate::{decode, decoding::to_normal_digit, error::Kind, Error, Result};
+
+/// The symbols used for check digits, per the spec. The first 32 match the ordinary alphabet.
+pub(crate) static CHECK_SYMBOLS: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ*~$=U";
+
+#[cfg(feature = "alloc")]
+/// Computes the check symbol for a sequence of digit values, most significant first.
+///
+/// The check symbol is the value of the digits, taken as one number, modulo 37.
+pub(crate) fn check_symbol<I: IntoIterator<Item = u8>>(digits: I) -> u8 {
+    let modulus = digits
+        .into_iter()
+        .fold(0u32, |m, digit| (m * 32 + u32::from(digit)) % 37);
+    CHECK_SYMBOLS[modulus as usize]
+}
+
+/// Reads a check symbol at the provided index, returning it in canonical form.
+///
+/// Check symbols fold the same way digits do, case-insensitively and with `O`, `I` and `L` read
+/// as `0` and `1`, with the five extra symbols `*~$=U` on top.
+pub(crate) fn read_check_symbol(idx: usize, u: u8) -> Result<u8> {
+    match to_normal_digit(idx, u) {
+        Ok(digit) => Ok(CHECK_SYMBOLS[usize::from(digit)]),
+        Err(_) if CHECK_SYMBOLS.contains(&u.to_ascii_uppercase()) => Ok(u.to_ascii_uppercase()),
+        Err(_) => Err(Error::new(
+            Kind::InvalidDigit(idx, u),
+            "Invalid check symbol.",
+        )),
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Returns the value of a canonical symbol taken from the uppercase alphabet.
+///
+/// # Panics
+///
+/// Panics if the symbol is not in the uppercase alphabet.
+pub(crate) fn digit_value(symbol: u8) -> u8 {
+    UPPERCASE_ENCODING
+        .iter()
+        .position(|&u| u == symbol)
+        .expect("symbol is from the alphabet") as u8
+}
+
+#[cfg(feature = "alloc")]
+/// Encodes a `u64` value as a Crockford Base32-encoded string followed by its check symbol.
+///
+/// The check symbol is the value modulo 37, written with the ordinary digits plus the five extra
+/// symbols `*~$=U`. It catches any single mistyped digit and most transpositions.
+///
+/// ```rust
+/// // 5111 % 37 = 5
+/// assert_eq!("4ZQ5", crockford::encode_with_check(5111));
+/// assert_eq!("14U", crockford::encode_with_check(36));
+/// ```
+pub fn encode_with_check(n: u64) -> String {
+    let mut s = String::with_capacity(crate::encoded_len(n) + 1);
+    crate::encode_into(n, &mut s);
+    s.push(char::from(CHECK_SYMBOLS[(n % 37) as usize]));
+    s
+}
+
+/// Attempts to decode a Crockford Base32-encoded string ending in a check symbol into a `u64`
+/// value.
+///
+/// This is the inverse of `encode_with_check`. The check symbol is read case-insensitively and
+/// folds ambiguous symbols the same way digits do. If it does not match the value, this fails
+/// with an error whose kind is `InvalidChecksum`.
+///
+/// ```rust
+/// assert_eq!(5111, crockford::decode_with_check("4zq5").unwrap());
+/// assert!(crockford::decode_with_check("4ZQ6").is_err());
+/// ```
+pub fn decode_with_check<T: AsRef<str>>(input: T) -> Result<u64> {
+    let input = input.as_ref();
+    let (&check, body) = input
+        .as_bytes()
+        .split_last()
+        .ok_or_else(|| Error::new(Kind::EmptyString, "Encoded input string is empty."))?;
+
+    // Every check symbol is a single ASCII byte, so once the last byte is known to be ASCII the
+    // rest is still a valid string.
+    let idx = body.len();
+    if !check.is_ascii() {
+        return Err(Error::new(
+            Kind::InvalidDigit(idx, check),
+            "Invalid check symbol.",
+        ));
+    }
+    let n = decode(&input[..idx])?;
+    let check = read_check_symbol(idx, check)?;
+
+    if check == CHECK_SYMBOLS[(n % 37) as usize] {
+        Ok(n)
+    } else {
+        Err(Error::new(
+            Kind::InvalidChecksum,
+            "Check symbol does not match.",
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::check_symbol;
+    use crate::{decode_with_check, encode_with_check, error::Kind, Error};
+
+    #[test]
+    fn check_symbol_is_value_mod_37() {
+        // 5111 is 4ZQ; 5111 % 37 = 5
+        assert_eq!(b'5', check_symbol(vec![4, 31, 23]));
+        assert_eq!(b'0', check_symbol(vec![1, 5]));
+        assert_eq!(b'U', check_symbol(vec![1, 4]));
+    }
+
+    #[test]
+    fn check_symbol_follows_the_digits() {
+        assert_eq!("00", encode_with_check(0));
+        assert_eq!("4ZQ5", encode_with_check(5111));
+        assert_eq!("14U", encode_with_check(36));
+        assert_eq!("10*", encode_with_check(32));
+    }
+
+    #[test]
+    fn round_trips() {
+        for &n in &[0, 1, 36, 37, 5111, u64::MAX] {
+            assert_eq!(Ok(n), decode_with_check(encode_with_check(n)));
+        }
+    }
+
+    #[test]
+    fn check_symbols_fold_like_digits() {
+        // 1 % 37 = 1, which may be typed as I or L.
+        assert_eq!(Ok(1), decode_with_check("1i"));
+        assert_eq!(Ok(1), decode_with_check("1L"));
+        assert_eq!(Ok(36), decode_with_check("14u"));
+    }
+
+    #[test]
+    fn mismatched_check_symbols_fail() {
+        let expected = Err(Error::new(Kind::InvalidChecksum, "Don't care"));
+
+        assert_eq!(expected, decode_with_check("4ZQ6"));
+        assert_eq!(expected, decode_with_check("Z4Q5"));
+    }
+
+    #[test]
+    fn malformed_input_fails() {
+        assert_eq!(
+            Err(Error::new(Kind::EmptyString, "Don't care")),
+            decode_with_check("")
+        );
+        assert_eq!(
+            Err(Error::new(Kind::EmptyString, "Don't care")),
+            decode_with_check("5")
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(0, 0), "Don't care")),
+            decode_with_check("4ZQ!")
+        );
+        assert_eq!(
+            Err(Error::new(Kind::CheckDigitUnsupported(0, 0), "Don't care")),
+            decode_with_check("4UQ5")
+        );
+    }
+
+    #[test]
+    fn non_ascii_check_symbols_fail() {
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(0, 0), "Don't care")),
+            decode_with_check("4ZQé")
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(0, 0), "Don't care")),
+            decode_with_check("é")
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Batches of unique, checksummed codes for coupons, vouchers and the like.
+//!
+//! Each code is a caller-chosen prefix, a fixed number of random symbols, and a trailing check
+//! symbol computed per the spec over the random symbols. Codes containing common obscenities are
+//! never issued.
+//!
+//! ```rust
+//! use crockford::codes::{self, Batch};
+//!
+//! let batch = Batch::generate(&mut rand::thread_rng(), 100, "SPRING-", 8);
+//!
+//! assert_eq!(100, batch.len());
+//! for code in &batch {
+//!     assert_eq!("SPRING-".len() + 9, code.len());
+//!     assert!(codes::verify(code, "SPRING-").is_ok());
+//! }
+//! ```
+
+use std::{collections::HashSet, io, slice};
+
+use rand::Rng;
+
+use crate::{
+    check::{check_symbol, digit_value, read_check_symbol},
+    decoding::to_normal_digit,
+    error::Kind,
+    CrockfordAlphanumeric, Error, Result,
+};
+
+/// Fragments that are never allowed to appear in an issued code.
+///
+/// The alphabet already lacks `I`, `L`, `O` and `U`, which rules out most words; this catches a
+/// few of the more obvious ones that remain.
+static DENYLIST: &[&str] = &[
+    "ASS", "CNT", "CVNT", "DCK", "DMN", "FAG", "FCK", "FK", "FVK", "KKK", "NAZ", "NGR", "PNS",
+    "PRN", "SEX", "SHT", "TWAT", "WANK", "XXX",
+];
+
+/// A set of unique codes, in the order they were generated.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Batch {
+    codes: Vec<String>,
+}
+
+impl Batch {
+    /// Generates `count` unique codes, each with the provided prefix and number of random symbols.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is more than half the number of possible codes of the provided length,
+    /// since finding that many unique codes would take unreasonably long.
+    pub fn generate<R: Rng + ?Sized>(rng: &mut R, count: usize, prefix: &str, len: usize) -> Batch {
+        let possible = 32u128.checked_pow(len as u32).unwrap_or(u128::MAX);
+        assert!(
+            count as u128 <= possible / 2,
+            "too many codes requested for the code length"
+        );
+
+        let mut seen = HashSet::with_capacity(count);
+        let mut codes = Vec::with_capacity(count);
+        let mut body = Vec::with_capacity(len);
+
+        while codes.len() < count {
+            body.clear();
+            body.extend(rng.sample_iter(CrockfordAlphanumeric).take(len));
+
+            if is_profane(&body) || !seen.insert(body.clone()) {
+                continue;
+            }
+
+            let check = check_symbol(body.iter().map(|&u| digit_value(u)));
+            let mut code = String::with_capacity(prefix.len() + len + 1);
+            code.push_str(prefix);
+            code.extend(body.iter().map(|&u| char::from(u)));
+            code.push(char::from(check));
+            codes.push(code);
+        }
+
+        Batch { codes }
+    }
+
+    /// Returns the number of codes in the batch.
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Returns true if the batch has no codes.
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Iterates over the codes in the batch.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.codes.iter())
+    }
+
+    /// Writes the batch as a single-column CSV file with a `code` header.
+    pub fn write_csv<W: io::Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(b"code\n")?;
+        for code in &self.codes {
+            if code.contains([',', '"', '\n', '\r']) {
+                writeln!(w, "\"{}\"", code.replace('"', "\"\""))?;
+            } else {
+                writeln!(w, "{}", code)?;
+            }
+        }
+        w.flush()
+    }
+
+    /// Returns the codes as a vector of strings.
+    pub fn into_vec(self) -> Vec<String> {
+        self.codes
+    }
+}
+
+/// Iterates over the codes in a batch.
+#[derive(Clone, Debug)]
+pub struct Iter<'a>(slice::Iter<'a, String>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        self.0.next().map(String::as_str)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a> IntoIterator for &'a Batch {
+    type Item = &'a str;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Batch {
+    type Item = String;
+    type IntoIter = std::vec::IntoIter<String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.codes.into_iter()
+    }
+}
+
+/// Verifies that a code has the provided prefix and a correct check symbol.
+///
+/// Everything after the prefix is read case-insensitively, with the usual folding of ambiguous
+/// symbols, so codes typed in by hand verify as long as they were not mistyped. This says
+/// nothing about whether the code was actually issued.
+pub fn verify(code: &str, prefix: &str) -> Result<()> {
+    let rest = code
+        .get(prefix.len()..)
+        .filter(|_| code.starts_with(prefix))
+        .ok_or_else(|| {
+            Error::new(
+                Kind::InvalidFormat,
+                "Code does not have the expected prefix.",
+            )
+        })?;
+
+    let (check, body) = match rest.as_bytes().split_last() {
+        Some((&check, body)) if !body.is_empty() => (check, body),
+        _ => return Err(Error::new(Kind::InvalidLength, "Code is too short.")),
+    };
+
+    let mut digits = Vec::with_capacity(body.len());
+    for (idx, &u) in body.iter().enumerate() {
+        digits.push(to_normal_digit(prefix.len() + idx, u)?);
+    }
+
+    if read_check_symbol(code.len() - 1, check)? == check_symbol(digits) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            Kind::InvalidChecksum,
+            "Code check symbol does not match.",
+        ))
+    }
+}
+
+fn is_profane(body: &[u8]) -> bool {
+    DENYLIST
+        .iter()
+        .any(|word| body.windows(word.len()).any(|w| w == word.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_profane, verify, Batch};
+    use crate::{error::Kind, Error};
+
+    #[test]
+    fn codes_are_unique() {
+        // With only two symbols per code, duplicates are all but certain to come up.
+        let batch = Batch::generate(&mut rand::thread_rng(), 512, "", 2);
+        let mut codes = batch.clone().into_vec();
+        codes.sort();
+        codes.dedup();
+
+        assert_eq!(512, codes.len());
+        assert_eq!(512, batch.len());
+    }
+
+    #[test]
+    fn codes_verify() {
+        let batch = Batch::generate(&mut rand::thread_rng(), 50, "X-", 6);
+
+        for code in &batch {
+            assert_eq!(Ok(()), verify(code, "X-"));
+            assert_eq!(
+                Ok(()),
+                verify(&code.to_lowercase().replacen("x-", "X-", 1), "X-")
+            );
+        }
+    }
+
+    #[test]
+    fn mistyped_codes_fail() {
+        let expected = Err(Error::new(Kind::InvalidChecksum, "Don't care"));
+
+        // 4ZQ is 5111, and 5111 % 37 is 5.
+        assert_eq!(Ok(()), verify("4ZQ5", ""));
+        assert_eq!(expected, verify("4ZQ6", ""));
+        assert_eq!(expected, verify("4QZ5", ""));
+
+        // Check symbols are case-insensitive and fold ambiguous symbols too.
+        assert_eq!(Ok(()), verify("14u", ""));
+        assert_eq!(Ok(()), verify("16i", ""));
+    }
+
+    #[test]
+    fn wrong_prefixes_fail() {
+        let expected = Err(Error::new(Kind::InvalidFormat, "Don't care"));
+
+        assert_eq!(expected, verify("Y-4ZQ5", "X-"));
+        assert_eq!(expected, verify("X_4ZQ5", "X-"));
+    }
+
+    #[test]
+    fn short_codes_fail() {
+        let expected = Err(Error::new(Kind::InvalidLength, "Don't care"));
+
+        assert_eq!(expected, verify("X-", "X-"));
+        assert_eq!(expected, verify("X-5", "X-"));
+    }
+
+    #[test]
+    fn profane_fragments_are_detected() {
+        assert!(is_profane(b"ZZFCKZZ"));
+        assert!(is_profane(b"XXX"));
+        assert!(!is_profane(b"ZZFZCK"));
+    }
+
+    #[test]
+    fn csv_has_a_header_and_one_code_per_line() {
+        let batch = Batch::generate(&mut rand::thread_rng(), 3, "A,", 4);
+        let mut csv = Vec::new();
+        batch.write_csv(&mut csv).unwrap();
+
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+
+        assert_eq!(4, lines.len());
+        assert_eq!("code", lines[0]);
+        assert!(lines[1..].iter().all(|line| line.starts_with("\"A,")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn oversized_batches_panic() {
+        Batch::generate(&mut rand::thread_rng(), 17, "", 1);
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    fmt, process,
+    sync::atomic::{AtomicU32, Ordering},
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rand::Rng;
+use sha3::{Digest, Sha3_512};
+
+use crate::{CrockfordAlphanumeric, UPPERCASE_ENCODING};
+
+/// The letters of the alphabet, from which the leading symbol of every ID is drawn.
+static LETTERS: &[u8] = b"ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Generates collision-resistant IDs following the cuid2 recipe, shared between any number of
+/// threads.
+///
+/// Each ID is a random leading letter followed by the SHA3-512 hash of the current time, fresh
+/// random salt, a per-generator counter and a fingerprint of the host process, rendered in
+/// Crockford digits. IDs are 24 symbols long by default and, unlike the reference implementation,
+/// uppercase; they decode the same either way.
+///
+/// The counter is atomic and randomness comes from the thread-local random number generator, so
+/// no lock is needed.
+///
+/// ```rust
+/// use crockford::Cuid2Generator;
+///
+/// let generator = Cuid2Generator::new().length(10);
+/// let id = generator.generate();
+///
+/// assert_eq!(10, id.len());
+/// assert!(id.as_bytes()[0].is_ascii_alphabetic());
+/// assert_ne!(id, std::thread::scope(|scope| scope.spawn(|| generator.generate()).join().unwrap()));
+/// ```
+pub struct Cuid2Generator {
+    counter: AtomicU32,
+    fingerprint: String,
+    length: usize,
+}
+
+impl Cuid2Generator {
+    /// Creates a generator.
+    pub fn new() -> Cuid2Generator {
+        let mut rng = rand::thread_rng();
+
+        // As in the reference implementation, the counter starts somewhere random so that
+        // generators started at the same moment do not walk in step.
+        let counter = rng.gen_range(0..476_782_367);
+
+        let mut hasher = Sha3_512::new();
+        hasher.update(process::id().to_string());
+        hasher.update(format!("{:?}", thread::current().id()));
+        hasher.update(entropy(&mut rng, 32));
+        let fingerprint = to_symbols(&hasher.finalize(), 32);
+
+        Cuid2Generator {
+            counter: AtomicU32::new(counter),
+            fingerprint,
+            length: 24,
+        }
+    }
+
+    /// Sets the number of symbols in each ID.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless the length is between 2 and 32.
+    pub fn length(mut self, length: usize) -> Cuid2Generator {
+        assert!((2..=32).contains(&length), "IDs must have 2 to 32 symbols");
+        self.length = length;
+        self
+    }
+
+    /// Generates an ID.
+    pub fn generate(&self) -> String {
+        let mut rng = rand::thread_rng();
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or(0);
+        let salt = entropy(&mut rng, self.length);
+        // Only distinctness matters, so relaxed ordering is enough.
+        let counter = self.counter.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+
+        let mut hasher = Sha3_512::new();
+        hasher.update(time.to_string());
+        hasher.update(salt);
+        hasher.update(counter.to_string());
+        hasher.update(&self.fingerprint);
+
+        let mut id = String::with_capacity(self.length);
+        id.push(char::from(LETTERS[rng.gen_range(0..LETTERS.len())]));
+        id.push_str(&to_symbols(&hasher.finalize(), self.length - 1));
+        id
+    }
+}
+
+impl Default for Cuid2Generator {
+    fn default() -> Cuid2Generator {
+        Cuid2Generator::new()
+    }
+}
+
+impl Clone for Cuid2Generator {
+    fn clone(&self) -> Cuid2Generator {
+        Cuid2Generator {
+            counter: AtomicU32::new(self.counter.load(Ordering::Relaxed)),
+            fingerprint: self.fingerprint.clone(),
+            length: self.length,
+        }
+    }
+}
+
+/// Yields IDs indefinitely.
+impl Iterator for Cuid2Generator {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        Some(self.generate())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Yields IDs indefinitely from a shared generator.
+impl Iterator for &Cuid2Generator {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        Some(self.generate())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl fmt::Debug for Cuid2Generator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The fingerprint and counter are what keep IDs unpredictable, so leave them out.
+        f.debug_struct("Cuid2Generator")
+            .field("length", &self.length)
+            .finish_non_exhaustive()
+    }
+}
+
+fn entropy<R: Rng>(rng: &mut R, len: usize) -> String {
+    rng.sample_iter(CrockfordAlphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+/// Renders the leading bits of a hash as `len` symbols.
+///
+/// The reference implementation converts the whole hash to one big base-36 number and drops the
+/// biased leading digit; taking five bits at a time is unbiased to begin with.
+fn to_symbols(hash: &[u8], len: usize) -> String {
+    debug_assert!(len * 5 <= hash.len() * 8);
+
+    (0..len)
+        .map(|idx| {
+            let bit = idx * 5;
+            let pair = u16::from(hash[bit / 8]) << 8
+                | u16::from(hash.get(bit / 8 + 1).copied().unwrap_or(0));
+            let digit = (pair >> (11 - bit % 8)) & 0x1F;
+            char::from(UPPERCASE_ENCODING[usize::from(digit)])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::to_symbols;
+    use crate::{Cuid2Generator, UPPERCASE_ENCODING};
+
+    #[test]
+    fn ids_have_the_configured_length() {
+        for length in 2..=32 {
+            assert_eq!(
+                length,
+                Cuid2Generator::new().length(length).generate().len()
+            );
+        }
+    }
+
+    #[test]
+    fn ids_start_with_a_letter_and_use_the_alphabet() {
+        let generator = Cuid2Generator::new().length(13);
+
+        for _ in 0..100 {
+            let id = generator.generate();
+            assert!(id.as_bytes()[0].is_ascii_alphabetic());
+            assert!(id.bytes().all(|u| UPPERCASE_ENCODING.contains(&u)));
+        }
+    }
+
+    #[test]
+    fn ids_do_not_collide() {
+        let generator = Cuid2Generator::new().length(8);
+        let ids: HashSet<_> = (0..10_000).map(|_| generator.generate()).collect();
+
+        assert_eq!(10_000, ids.len());
+    }
+
+    #[test]
+    fn shared_generators_do_not_collide() {
+        let generator = Cuid2Generator::new().length(8);
+        let ids: HashSet<_> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| (&generator).take(1000).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+
+        assert_eq!(4000, ids.len());
+    }
+
+    #[test]
+    fn generators_are_iterators() {
+        let ids: HashSet<_> = Cuid2Generator::new().take(100).collect();
+        assert_eq!(100, ids.len());
+    }
+
+    #[test]
+    fn hashes_are_read_five_bits_at_a_time() {
+        assert_eq!("Z0Z0", to_symbols(&[0b1111_1000, 0b0011_1110, 0], 4));
+        assert_eq!("00001", to_symbols(&[0, 0, 0, 0b1111_1111, 0], 5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn oversized_lengths_panic() {
+        let _ = Cuid2Generator::new().length(33);
+    }
+}
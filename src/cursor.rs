@@ -0,0 +1,181 @@
+//! Opaque pagination cursors.
+//!
+//! A cursor is a small serde value, serialized to JSON and rendered as a single Crockford string
+//! with `encode_bytes`. The result is URL-safe and case-insensitive, but not secret: anyone can
+//! decode it. With the `signed` feature, `encode_signed` appends a truncated HMAC-SHA256 of the
+//! serialized bytes so that cursors handed back by clients can be checked for tampering.
+//!
+//! ```rust
+//! # use crockford::Error;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Page {
+//!     after: u64,
+//!     limit: u8,
+//! }
+//!
+//! # fn run() -> Result<(), Error> {
+//! let page = Page { after: 5111, limit: 20 };
+//! let cursor = crockford::cursor::encode(&page)?;
+//!
+//! assert_eq!(page, crockford::cursor::decode(&cursor)?);
+//! assert_eq!(page, crockford::cursor::decode(cursor.to_lowercase())?);
+//! # Ok(())
+//! # }
+//! # run().unwrap()
+//! ```
+
+use serde::{de::DeserializeOwned, Serialize};
+
+#[cfg(feature = "signed")]
+use crate::signed::Keyring;
+use crate::{decode_bytes, encode_bytes, error::Kind, Error, Result};
+
+/// Length in bytes of the truncated signature appended by `encode_signed`.
+#[cfg(feature = "signed")]
+const SIGNATURE_LEN: usize = 16;
+
+/// Renders a value as a cursor.
+///
+/// Fails only if the value cannot be serialized to JSON, e.g. a map with non-string keys.
+pub fn encode<T: Serialize>(value: &T) -> Result<String> {
+    Ok(encode_bytes(&serialize(value)?))
+}
+
+/// Reads a value back from a cursor.
+pub fn decode<T: DeserializeOwned, S: AsRef<str>>(cursor: S) -> Result<T> {
+    deserialize(&decode_bytes(cursor)?)
+}
+
+/// Renders a value as a cursor signed with the keyring's primary key.
+#[cfg(feature = "signed")]
+pub fn encode_signed<T: Serialize>(value: &T, keyring: &Keyring) -> Result<String> {
+    let mut bytes = serialize(value)?;
+    let signature = keyring.sign_bytes(&bytes);
+    bytes.extend_from_slice(&signature);
+    Ok(encode_bytes(&bytes))
+}
+
+/// Reads a value back from a signed cursor.
+///
+/// Fails with `Kind::InvalidSignature` if no key in the keyring produced the cursor's signature.
+#[cfg(feature = "signed")]
+pub fn decode_signed<T: DeserializeOwned, S: AsRef<str>>(
+    cursor: S,
+    keyring: &Keyring,
+) -> Result<T> {
+    let bytes = decode_bytes(cursor)?;
+    if bytes.len() < SIGNATURE_LEN {
+        return Err(Error::new(
+            Kind::InvalidSignature,
+            "Cursor has no signature.",
+        ));
+    }
+
+    let (payload, signature) = bytes.split_at(bytes.len() - SIGNATURE_LEN);
+    if !keyring.verify_bytes(payload, signature) {
+        return Err(Error::new(
+            Kind::InvalidSignature,
+            "Cursor signature is invalid.",
+        ));
+    }
+
+    deserialize(payload)
+}
+
+fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    serde_json::to_vec(value)
+        .map_err(|_| Error::new(Kind::InvalidFormat, "Cursor value cannot be serialized."))
+}
+
+fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    serde_json::from_slice(bytes)
+        .map_err(|_| Error::new(Kind::InvalidFormat, "Cursor payload is malformed."))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{encode_bytes, error::Kind, Error};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Page {
+        after: String,
+        limit: u8,
+    }
+
+    fn page() -> Page {
+        Page {
+            after: "4ZQ".into(),
+            limit: 20,
+        }
+    }
+
+    #[test]
+    fn cursors_round_trip() {
+        let cursor = super::encode(&page()).unwrap();
+
+        assert!(cursor.bytes().all(|u| u.is_ascii_alphanumeric()));
+        assert_eq!(Ok(page()), super::decode(&cursor));
+    }
+
+    #[test]
+    fn malformed_payloads_fail() {
+        let cursor = encode_bytes(b"{\"after\":");
+        let expected: Result<Page, _> = Err(Error::new(Kind::InvalidFormat, "Don't care"));
+
+        assert_eq!(expected, super::decode(cursor));
+    }
+
+    #[cfg(feature = "signed")]
+    mod signed {
+        use super::{page, Page};
+        use crate::{encode_bytes, error::Kind, signed::Keyring, Error};
+
+        #[test]
+        fn signed_cursors_round_trip() {
+            let keyring = Keyring::new(b"secret");
+            let cursor = crate::cursor::encode_signed(&page(), &keyring).unwrap();
+
+            assert_eq!(Ok(page()), crate::cursor::decode_signed(&cursor, &keyring));
+            assert_eq!(
+                Ok(page()),
+                crate::cursor::decode_signed(cursor.to_lowercase(), &keyring)
+            );
+        }
+
+        #[test]
+        fn tampered_cursors_fail() {
+            let keyring = Keyring::new(b"secret");
+            let mut bytes = serde_json::to_vec(&Page {
+                after: "4ZR".into(),
+                limit: 20,
+            })
+            .unwrap();
+            bytes.extend_from_slice(&keyring.sign_bytes(b"{\"after\":\"4ZQ\",\"limit\":20}"));
+            let expected: Result<Page, _> = Err(Error::new(Kind::InvalidSignature, "Don't care"));
+
+            assert_eq!(
+                expected,
+                crate::cursor::decode_signed(encode_bytes(&bytes), &keyring)
+            );
+        }
+
+        #[test]
+        fn unsigned_cursors_fail() {
+            let keyring = Keyring::new(b"secret");
+            let cursor = crate::cursor::encode(&page()).unwrap();
+            let other = crate::cursor::encode_signed(&page(), &Keyring::new(b"other")).unwrap();
+            let expected: Result<Page, _> = Err(Error::new(Kind::InvalidSignature, "Don't care"));
+
+            assert_eq!(expected, crate::cursor::decode_signed(cursor, &keyring));
+            assert_eq!(expected, crate::cursor::decode_signed(other, &keyring));
+            assert_eq!(
+                expected,
+                crate::cursor::decode_signed(encode_bytes(b"{}"), &keyring)
+            );
+        }
+    }
+}
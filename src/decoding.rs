@@ -1,10 +1,30 @@
+use core::convert::TryFrom;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use crate::{error::Kind, Error, Result};
 
 const BASE: u64 = 0x20;
 
+/// Maps each byte to its digit value, or to -1 for invalid digits and -2 for check digits.
+const VALUE_MAPPING: [i8; 256] = include!("../resources/u8-mapping.txt");
+
 /// Attempts to decode a Crockford Base32-encoded string into a `u64` value.
+///
+/// Values that do not fit in a `u64` fail with `OutOfRange`. That includes thirteen-digit input
+/// whose leading digit is above `F`, which once wrapped around silently.
+///
+/// ```rust
+/// assert_eq!(u64::MAX, crockford::decode("FZZZZZZZZZZZZ").unwrap());
+/// assert!(crockford::decode("G000000000000").is_err());
+/// ```
 pub fn decode<T: AsRef<str>>(input: T) -> Result<u64> {
-    let input = input.as_ref();
+    decode_raw(input.as_ref().as_bytes())
+}
+
+/// Attempts to decode raw bytes, which need not be valid UTF-8, into a `u64` value.
+pub(crate) fn decode_raw(input: &[u8]) -> Result<u64> {
     match input.len() {
         0 => Err(Error::new(
             Kind::EmptyString,
@@ -17,8 +37,14 @@ pub fn decode<T: AsRef<str>>(input: T) -> Result<u64> {
             let mut place = BASE.pow(input.len() as u32 - 1);
             let mut n = 0;
 
-            for (idx, u) in input.bytes().enumerate() {
+            for (idx, &u) in input.iter().enumerate() {
                 let digit = to_normal_digit(idx, u)?;
+
+                // A thirteen-digit value only has room for four bits in its leading digit.
+                if idx == 0 && input.len() == 13 && digit > 0xF {
+                    return Err(Error::new(Kind::OutOfRange, "Encoded value is too large"));
+                }
+
                 n += u64::from(digit).wrapping_mul(place);
                 place >>= 5;
             }
@@ -28,12 +54,267 @@ pub fn decode<T: AsRef<str>>(input: T) -> Result<u64> {
     }
 }
 
+/// Attempts to decode a Crockford Base32-encoded string into a `u64` value at compile time.
+///
+/// This accepts and rejects exactly what `decode` does, but can be evaluated in a constant. It
+/// is slower than `decode` at run time, so prefer that outside of constants.
+///
+/// ```rust
+/// const ADMIN_ID: u64 = match crockford::decode_const("4ZQ") {
+///     Ok(n) => n,
+///     Err(_) => panic!("invalid admin ID"),
+/// };
+///
+/// assert_eq!(5111, ADMIN_ID);
+/// ```
+pub const fn decode_const(input: &str) -> Result<u64> {
+    let input = input.as_bytes();
+    if input.is_empty() {
+        return Err(Error::new(
+            Kind::EmptyString,
+            "Encoded input string is empty.",
+        ));
+    }
+    if input.len() > 13 {
+        return Err(Error::new(Kind::OutOfRange, "Encoded value is too large"));
+    }
+
+    let mut n = 0;
+    let mut idx = 0;
+    while idx < input.len() {
+        let u = input[idx];
+        let digit = match VALUE_MAPPING[u as usize] {
+            -1 => {
+                return Err(Error::new(
+                    Kind::InvalidDigit(idx, u),
+                    "Invalid encoded digit.",
+                ))
+            }
+            -2 => {
+                return Err(Error::new(
+                    Kind::CheckDigitUnsupported(idx, u),
+                    "Check symbol is only valid in check position.",
+                ))
+            }
+            digit => digit as u64,
+        };
+
+        // A thirteen-digit value only has room for four bits in its leading digit.
+        if idx == 0 && input.len() == 13 && digit > 0xF {
+            return Err(Error::new(Kind::OutOfRange, "Encoded value is too large"));
+        }
+
+        n = n << 5 | digit;
+        idx += 1;
+    }
+
+    Ok(n)
+}
+
+/// Attempts to decode a Crockford Base32-encoded string into a `u64` value, accepting only
+/// canonical digits.
+///
+/// `decode` reads input the way the spec asks, case-insensitively and with `O`, `I` and `L`
+/// folded into `0` and `1`. That is right for input typed in by people, but it means many
+/// strings decode to the same value. This instead rejects lowercase and ambiguous symbols, so
+/// every value has exactly one accepted form, as produced by `encode`. A rejected symbol fails
+/// with `InvalidDigit`, carrying its index and byte.
+///
+/// ```rust
+/// assert_eq!(5111, crockford::decode_strict("4ZQ").unwrap());
+/// assert!(crockford::decode_strict("4zq").is_err());
+/// assert!(crockford::decode_strict("4ZO").is_err());
+/// ```
+pub fn decode_strict<T: AsRef<str>>(input: T) -> Result<u64> {
+    use crate::UPPERCASE_ENCODING;
+
+    let input = input.as_ref().as_bytes();
+    for (idx, &u) in input.iter().enumerate() {
+        let digit = to_normal_digit(idx, u)?;
+        if UPPERCASE_ENCODING[usize::from(digit)] != u {
+            return Err(Error::new(
+                Kind::InvalidDigit(idx, u),
+                "Encoded digit is not canonical.",
+            ));
+        }
+    }
+
+    decode_raw(input)
+}
+
+/// Attempts to decode a Crockford Base32-encoded string into a `u64` value, ignoring hyphens.
+///
+/// The spec allows hyphens anywhere in a symbol string, to be ignored when decoding. `decode`
+/// rejects them, since formats such as `decode_signed_display` and `from_leb128` give `-` a
+/// meaning of its own; this accepts everything `decode` does and skips every hyphen, the way
+/// `decode_grouped` does with `-` as the separator.
+///
+/// ```rust
+/// assert_eq!(5111, crockford::decode_lenient("4z-q").unwrap());
+/// assert_eq!(5111, crockford::decode_lenient("4ZQ").unwrap());
+/// assert!(crockford::decode_lenient("-").is_err());
+/// ```
+pub fn decode_lenient<T: AsRef<str>>(input: T) -> Result<u64> {
+    decode_grouped(input, '-')
+}
+
+/// Attempts to decode a Crockford Base32-encoded string into a `u64` value, ignoring every
+/// occurrence of the provided separator.
+///
+/// This is the inverse of `encode_grouped`. Separators may appear anywhere, including where
+/// `encode_grouped` would not put them, so codes re-grouped by hand still decode. Error indices
+/// refer to the input as given, separators included.
+///
+/// ```rust
+/// # use crockford::Error;
+/// # fn run() -> Result<(), Error> {
+/// assert_eq!(1234567890123, crockford::decode_grouped("13xr-zp16-b", '-')?);
+/// assert_eq!(1234567890123, crockford::decode_grouped("13X-RZP-16B", '-')?);
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+pub fn decode_grouped<T: AsRef<str>>(input: T, separator: char) -> Result<u64> {
+    let input = input.as_ref();
+    let mut n: u64 = 0;
+    let mut digits = 0;
+
+    for (idx, c) in input.char_indices() {
+        if c == separator {
+            continue;
+        }
+
+        // Non-ASCII characters start with a byte that is never a valid digit.
+        let digit = to_normal_digit(idx, input.as_bytes()[idx])?;
+        if n.leading_zeros() < 5 {
+            return Err(Error::new(Kind::OutOfRange, "Encoded value is too large"));
+        }
+        n = n << 5 | u64::from(digit);
+        digits += 1;
+    }
+
+    if digits == 0 {
+        return Err(Error::new(
+            Kind::EmptyString,
+            "Encoded input string is empty.",
+        ));
+    }
+
+    Ok(n)
+}
+
+/// Attempts to decode a Crockford Base32-encoded string with an optional leading `-` into an
+/// `i64` value.
+///
+/// This is the inverse of `encode_signed_display`.
+pub fn decode_signed_display<T: AsRef<str>>(input: T) -> Result<i64> {
+    let input = input.as_ref();
+    let (negative, magnitude) = match input.strip_prefix('-') {
+        Some(magnitude) => (true, decode(magnitude)?),
+        None => (false, decode(input)?),
+    };
+
+    // The magnitude of i64::MIN is one greater than i64::MAX.
+    match (negative, i64::try_from(magnitude)) {
+        (false, Ok(n)) => Ok(n),
+        (true, Ok(n)) => Ok(-n),
+        (true, Err(_)) if magnitude == i64::MIN.unsigned_abs() => Ok(i64::MIN),
+        _ => Err(Error::new(Kind::OutOfRange, "Encoded value is too large")),
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Attempts to decode a list of values separated by any of the provided characters.
+///
+/// Whitespace around each value is ignored, and an input that is empty or all whitespace decodes
+/// to an empty list. Any other empty element, such as one left by a trailing separator, is an
+/// error. When a value fails to decode, `Error::element` says which one.
+///
+/// ```rust
+/// let values = crockford::decode_list("4ZQ, 1 ;fzzzzzzzzzzzz", &[',', ';']).unwrap();
+/// assert_eq!(vec![5111, 1, u64::MAX], values);
+///
+/// let error = crockford::decode_list("4ZQ, 1, 4!", &[',']).unwrap_err();
+/// assert_eq!(Some(2), error.element());
+/// ```
+pub fn decode_list(input: &str, seps: &[char]) -> Result<Vec<u64>> {
+    if input.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    input
+        .split(seps)
+        .enumerate()
+        .map(|(element, value)| decode(value.trim()).map_err(|e| e.in_element(element)))
+        .collect()
+}
+
+/// Decodes input made of back-to-back fixed-width records of `W` digits each.
+///
+/// Each record may have leading zeros. Errors say which record failed through `Error::element`,
+/// and input that does not divide evenly into records yields a final `InvalidLength` error.
+///
+/// # Panics
+///
+/// Panics if `W` is zero.
+///
+/// ```rust
+/// let values: Vec<_> = crockford::decode_records::<4>("04ZQ0001ZZZZ")
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+///
+/// assert_eq!(vec![5111, 1, 0xFFFFF], values);
+/// ```
+pub fn decode_records<const W: usize>(input: &str) -> impl Iterator<Item = Result<u64>> + '_ {
+    assert!(W > 0, "records must have at least one digit");
+
+    input
+        .as_bytes()
+        .chunks(W)
+        .enumerate()
+        .map(|(element, record)| {
+            // Records are cut by byte, so they may not be valid UTF-8 on their own; decode the
+            // bytes directly.
+            decode_padded_bytes(record, W)
+                .and_then(|n| {
+                    u64::try_from(n)
+                        .map_err(|_| Error::new(Kind::OutOfRange, "Encoded value is too large"))
+                })
+                .map_err(|e| e.in_element(element))
+        })
+}
+
+/// Attempts to decode exactly `width` digits, allowing leading zeros, into a `u128` value.
+pub(crate) fn decode_padded(input: &str, width: usize) -> Result<u128> {
+    decode_padded_bytes(input.as_bytes(), width)
+}
+
+pub(crate) fn decode_padded_bytes(input: &[u8], width: usize) -> Result<u128> {
+    if input.len() != width {
+        return Err(Error::new(
+            Kind::InvalidLength,
+            "Encoded input has the wrong length.",
+        ));
+    }
+
+    let mut n: u128 = 0;
+    for (idx, &u) in input.iter().enumerate() {
+        let digit = to_normal_digit(idx, u)?;
+        if n.leading_zeros() < 5 {
+            return Err(Error::new(Kind::OutOfRange, "Encoded value is too large"));
+        }
+        n = n << 5 | u128::from(digit);
+    }
+
+    Ok(n)
+}
+
 /// Attempts to convert an ascii digit to a normalized form.
-fn to_normal_digit(idx: usize, u: u8) -> Result<u8> {
-    static VALUE_MAPPING: [i8; 256] = include!("../resources/u8-mapping.txt");
+pub(crate) fn to_normal_digit(idx: usize, u: u8) -> Result<u8> {
+    static MAPPING: [i8; 256] = VALUE_MAPPING;
 
     unsafe {
-        match VALUE_MAPPING.get_unchecked(u as usize) {
+        match MAPPING.get_unchecked(u as usize) {
             -1 => Err(Error::new(
                 Kind::InvalidDigit(idx, u),
                 "Invalid encoded digit.",
@@ -41,7 +322,7 @@ fn to_normal_digit(idx: usize, u: u8) -> Result<u8> {
 
             -2 => Err(Error::new(
                 Kind::CheckDigitUnsupported(idx, u),
-                "Check digits not currently supported.",
+                "Check symbol is only valid in check position.",
             )),
 
             &result => Ok(result as u8),
@@ -49,9 +330,13 @@ fn to_normal_digit(idx: usize, u: u8) -> Result<u8> {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
-    use crate::{decode, error::Kind, Error};
+    use super::decode_padded;
+    use crate::{
+        decode, decode_const, decode_grouped, decode_lenient, decode_list, decode_records,
+        decode_signed_display, decode_strict, encode, encode_grouped, error::Kind, Error,
+    };
 
     #[test]
     fn zero_length_strings_fail() {
@@ -71,6 +356,15 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn overflowing_strings_fail() {
+        let input = "g000000000000";
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+        let actual = decode(input);
+
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn invalid_bytes_fail() {
         let input = "fZZ!2";
@@ -137,4 +431,240 @@ mod tests {
         assert!(decode("iVuv").is_err());
         assert!(decode("iVUv").is_err());
     }
+
+    #[test]
+    fn check_symbols_are_reported_as_such() {
+        for (idx, &u) in b"*~$=Uu".iter().enumerate() {
+            let input = [b'4', u];
+            assert_eq!(
+                Err(Error::new(Kind::CheckDigitUnsupported(1, u), "Don't care")),
+                decode(core::str::from_utf8(&input).unwrap()),
+                "{}",
+                idx
+            );
+        }
+    }
+
+    #[test]
+    fn padded_values_decode() {
+        assert_eq!(Ok(5111), decode_padded("00004zq", 7));
+        assert_eq!(
+            Ok(u128::MAX),
+            decode_padded("7ZZZZZZZZZZZZZZZZZZZZZZZZZ", 26)
+        );
+    }
+
+    #[test]
+    fn padded_values_must_have_exact_width() {
+        let expected = Err(Error::new(Kind::InvalidLength, "Don't care"));
+        assert_eq!(expected, decode_padded("4zq", 7));
+    }
+
+    #[test]
+    fn padded_values_must_fit() {
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+        assert_eq!(expected, decode_padded("80000000000000000000000000", 26));
+    }
+
+    #[test]
+    fn signed_values_fit_i64() {
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+
+        assert_eq!(expected, decode_signed_display("8000000000000"));
+        assert_eq!(expected, decode_signed_display("-8000000000001"));
+        assert_eq!(Ok(0), decode_signed_display("-0"));
+    }
+
+    #[test]
+    fn lone_signs_fail() {
+        let expected = Err(Error::new(Kind::EmptyString, "Don't care"));
+        assert_eq!(expected, decode_signed_display("-"));
+    }
+
+    #[test]
+    fn lists_tolerate_whitespace_around_separators() {
+        assert_eq!(Ok(vec![]), decode_list(" \t", &[',']));
+        assert_eq!(Ok(vec![1]), decode_list("1", &[',']));
+        assert_eq!(Ok(vec![1, 2, 3]), decode_list(" 1 ,\t2|3 ", &[',', '|']));
+    }
+
+    #[test]
+    fn list_errors_name_the_element() {
+        let error = decode_list("1,2,,3", &[',']).unwrap_err();
+        assert_eq!(Error::new(Kind::EmptyString, "Don't care"), error);
+        assert_eq!(Some(2), error.element());
+
+        let error = decode_list("1, 2!", &[',']).unwrap_err();
+        assert_eq!(Error::new(Kind::InvalidDigit(0, 0), "Don't care"), error);
+        assert_eq!(Some(1), error.element());
+        assert_eq!(None, decode("2!").unwrap_err().element());
+    }
+
+    #[test]
+    fn records_split_by_width() {
+        let values: Vec<_> = decode_records::<1>("4ZQ").collect();
+        assert_eq!(vec![Ok(4), Ok(31), Ok(23)], values);
+
+        let values: Vec<_> = decode_records::<13>("").collect();
+        assert!(values.is_empty());
+
+        let values: Vec<_> = decode_records::<14>("0FZZZZZZZZZZZZ").collect();
+        assert_eq!(vec![Ok(u64::MAX)], values);
+    }
+
+    #[test]
+    fn record_errors_name_the_record() {
+        let values: Vec<_> = decode_records::<3>("0014!Z10000").collect();
+
+        assert_eq!(Ok(1), values[0]);
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(1, 0), "Don't care")),
+            values[1]
+        );
+        assert_eq!(Some(1), values[1].as_ref().unwrap_err().element());
+        assert_eq!(Ok(1024), values[2]);
+        assert_eq!(
+            Err(Error::new(Kind::InvalidLength, "Don't care")),
+            values[3]
+        );
+        assert_eq!(Some(3), values[3].as_ref().unwrap_err().element());
+    }
+
+    #[test]
+    fn oversized_records_fail() {
+        let values: Vec<_> = decode_records::<13>("G000000000000").collect();
+        assert_eq!(
+            vec![Err(Error::new(Kind::OutOfRange, "Don't care"))],
+            values
+        );
+    }
+
+    #[test]
+    fn const_decoding_matches_decode() {
+        for input in &[
+            "",
+            "0",
+            "4zq",
+            "oIl",
+            "FZZZZZZZZZZZZ",
+            "G000000000000",
+            "10000000000000",
+            "4!",
+            "4U",
+            "*",
+        ] {
+            assert_eq!(decode(input), decode_const(input), "{}", input);
+        }
+    }
+
+    #[test]
+    fn const_decoding_works_in_constants() {
+        const VALUE: u64 = match decode_const("FZZZZZZZZZZZZ") {
+            Ok(n) => n,
+            Err(_) => panic!("invalid value"),
+        };
+
+        assert_eq!(u64::MAX, VALUE);
+    }
+
+    #[test]
+    fn grouped_values_round_trip() {
+        for &n in &[0, 1, 5111, 1234567890123, u64::MAX] {
+            for size in 1..=13 {
+                assert_eq!(Ok(n), decode_grouped(encode_grouped(n, size, '-'), '-'));
+            }
+        }
+        assert_eq!(Ok(5111), decode_grouped("4 Z Q", ' '));
+        assert_eq!(Ok(5111), decode_grouped("4–ZQ", '–'));
+    }
+
+    #[test]
+    fn grouped_errors_point_into_the_original_input() {
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(5, b'!'), "Don't care")),
+            decode_grouped("4Z-Q-!", '-')
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(0, 0), "Don't care")),
+            decode_grouped("4Z Q", '-')
+        );
+    }
+
+    #[test]
+    fn grouped_input_without_digits_fails() {
+        let expected = Err(Error::new(Kind::EmptyString, "Don't care"));
+
+        assert_eq!(expected, decode_grouped("", '-'));
+        assert_eq!(expected, decode_grouped("---", '-'));
+    }
+
+    #[test]
+    fn grouped_overflow_fails() {
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+
+        assert_eq!(expected, decode_grouped("G000-0000-0000-0", '-'));
+        assert_eq!(Ok(u64::MAX), decode_grouped("F-ZZZZ-ZZZZ-ZZZZ", '-'));
+    }
+
+    #[test]
+    fn strict_decoding_accepts_encoder_output() {
+        for &n in &[0, 1, 5111, u64::MAX] {
+            assert_eq!(Ok(n), decode_strict(encode(n)));
+        }
+    }
+
+    #[test]
+    fn strict_decoding_rejects_non_canonical_digits() {
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(1, b'z'), "Don't care")),
+            decode_strict("4zQ")
+        );
+        for input in &["O", "I", "L", "i", "4Z-Q"] {
+            assert_eq!(
+                Err(Error::new(Kind::InvalidDigit(0, 0), "Don't care")),
+                decode_strict(input)
+            );
+        }
+    }
+
+    #[test]
+    fn strict_decoding_fails_like_decode_otherwise() {
+        assert_eq!(
+            Err(Error::new(Kind::EmptyString, "Don't care")),
+            decode_strict("")
+        );
+        assert_eq!(
+            Err(Error::new(Kind::OutOfRange, "Don't care")),
+            decode_strict("G000000000000")
+        );
+    }
+
+    #[test]
+    fn lenient_decoding_ignores_hyphens() {
+        assert_eq!(Ok(5111), decode_lenient("4ZQ"));
+        assert_eq!(Ok(5111), decode_lenient("-4z-Q-"));
+        assert_eq!(decode("4OZL"), decode_lenient("4-o-Z-l"));
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(0, 0), "Don't care")),
+            decode_lenient("4Z Q")
+        );
+        assert_eq!(
+            Err(Error::new(Kind::EmptyString, "Don't care")),
+            decode_lenient("--")
+        );
+    }
+
+    #[test]
+    fn errors_expose_the_offending_byte() {
+        let e = decode_strict("4Zq").unwrap_err();
+
+        assert_eq!(&Kind::InvalidDigit(2, b'q'), e.kind());
+        assert_eq!(Some(2), e.index());
+        assert_eq!(Some(b'q'), e.byte());
+
+        let e = decode_strict("").unwrap_err();
+        assert_eq!(&Kind::EmptyString, e.kind());
+        assert_eq!(None, e.index());
+        assert_eq!(None, e.byte());
+    }
 }
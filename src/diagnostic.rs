@@ -0,0 +1,316 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    check::{read_check_symbol, CHECK_SYMBOLS},
+    decoding::to_normal_digit,
+    error::Kind,
+    Error, Result, MAX_ENCODED_LEN_U64, UPPERCASE_ENCODING,
+};
+
+/// A full report on a successfully decoded value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Decoded {
+    /// The decoded value.
+    pub value: u64,
+
+    /// The input as it would be written with canonical uppercase symbols.
+    pub canonical: String,
+
+    /// Every ambiguous symbol that was read as something else.
+    pub corrections: Vec<Correction>,
+
+    /// Whether the check symbol matches the value.
+    ///
+    /// Only `decode_diagnostic_with_check` reads a check symbol; `decode_diagnostic` always
+    /// leaves this as `None`.
+    pub checksum: Option<ChecksumStatus>,
+
+    /// The number of digits in the input, not counting any check symbol.
+    pub width: usize,
+}
+
+/// An ambiguous symbol in the input and the symbol it was read as.
+///
+/// Per the spec, `O` is read as `0` and `I` and `L` are read as `1`. Lowercase input alone
+/// is not considered a correction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Correction {
+    /// The index of the symbol in the input.
+    pub index: usize,
+
+    /// The symbol as it appeared in the input.
+    pub found: u8,
+
+    /// The canonical symbol it was read as.
+    pub canonical: u8,
+}
+
+/// Whether the check symbol at the end of the input matches the value before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    /// The final symbol is the expected check symbol.
+    Valid,
+
+    /// The final symbol is not the expected check symbol.
+    Invalid {
+        /// The check symbol the value calls for.
+        expected: u8,
+    },
+}
+
+/// Attempts to decode a value while reporting everything a user might want to know about it.
+///
+/// This accepts exactly the same input as `decode`, but also returns the canonical form of the
+/// input and any corrections made along the way. Nothing is said about check symbols; use
+/// `decode_diagnostic_with_check` for input that ends in one.
+///
+/// ```rust
+/// # use crockford::{Correction, Error};
+/// # fn run() -> Result<(), Error> {
+/// let decoded = crockford::decode_diagnostic("4zo")?;
+///
+/// assert_eq!(5088, decoded.value);
+/// assert_eq!("4Z0", decoded.canonical);
+/// assert_eq!(
+///     vec![Correction { index: 2, found: b'o', canonical: b'0' }],
+///     decoded.corrections,
+/// );
+/// assert_eq!(None, decoded.checksum);
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+pub fn decode_diagnostic<T: AsRef<str>>(input: T) -> Result<Decoded> {
+    let input = input.as_ref();
+    match input.len() {
+        0 => Err(Error::new(
+            Kind::EmptyString,
+            "Encoded input string is empty.",
+        )),
+
+        n if n > MAX_ENCODED_LEN_U64 => {
+            Err(Error::new(Kind::OutOfRange, "Encoded value is too large"))
+        }
+
+        width => {
+            let mut value: u64 = 0;
+            let mut canonical = String::with_capacity(width);
+            let mut corrections = Vec::new();
+
+            for (index, found) in input.bytes().enumerate() {
+                let digit = to_normal_digit(index, found)?;
+                if value.leading_zeros() < 5 {
+                    return Err(Error::new(Kind::OutOfRange, "Encoded value is too large"));
+                }
+                value = value << 5 | u64::from(digit);
+
+                let symbol = UPPERCASE_ENCODING[digit as usize];
+                if symbol != found.to_ascii_uppercase() {
+                    corrections.push(Correction {
+                        index,
+                        found,
+                        canonical: symbol,
+                    });
+                }
+                canonical.push(char::from(symbol));
+            }
+
+            Ok(Decoded {
+                value,
+                canonical,
+                corrections,
+                checksum: None,
+                width,
+            })
+        }
+    }
+}
+
+/// Attempts to decode a value ending in a check symbol while reporting everything a user might
+/// want to know about it.
+///
+/// This accepts exactly the same input as `decode_with_check`, except that a check symbol that
+/// does not match is reported in `checksum` rather than as an error. The value, canonical form
+/// and width describe the digits before the check symbol.
+///
+/// ```rust
+/// # use crockford::{ChecksumStatus, Error};
+/// # fn run() -> Result<(), Error> {
+/// let decoded = crockford::decode_diagnostic_with_check("4zq6")?;
+///
+/// assert_eq!(5111, decoded.value);
+/// assert_eq!("4ZQ", decoded.canonical);
+/// assert_eq!(3, decoded.width);
+///
+/// // 5111 % 37 is 5.
+/// assert_eq!(Some(ChecksumStatus::Invalid { expected: b'5' }), decoded.checksum);
+/// assert_eq!(
+///     Some(ChecksumStatus::Valid),
+///     crockford::decode_diagnostic_with_check("4zq5")?.checksum,
+/// );
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+pub fn decode_diagnostic_with_check<T: AsRef<str>>(input: T) -> Result<Decoded> {
+    let input = input.as_ref();
+    let (&found, body) = input
+        .as_bytes()
+        .split_last()
+        .ok_or_else(|| Error::new(Kind::EmptyString, "Encoded input string is empty."))?;
+
+    // As in `decode_with_check`, an ASCII final byte leaves the rest a valid string.
+    let index = body.len();
+    if !found.is_ascii() {
+        return Err(Error::new(
+            Kind::InvalidDigit(index, found),
+            "Invalid check symbol.",
+        ));
+    }
+    let mut decoded = decode_diagnostic(&input[..index])?;
+    let check = read_check_symbol(index, found)?;
+
+    if check != found.to_ascii_uppercase() {
+        decoded.corrections.push(Correction {
+            index,
+            found,
+            canonical: check,
+        });
+    }
+
+    let expected = CHECK_SYMBOLS[(decoded.value % 37) as usize];
+    decoded.checksum = Some(if check == expected {
+        ChecksumStatus::Valid
+    } else {
+        ChecksumStatus::Invalid { expected }
+    });
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        decode, decode_diagnostic, decode_diagnostic_with_check, decode_with_check, encode,
+        encode_with_check, error::Kind, ChecksumStatus, Correction, Error, MAX_ENCODED_LEN_U64,
+    };
+
+    #[test]
+    fn clean_input_has_no_corrections() {
+        let decoded = decode_diagnostic("4zq").unwrap();
+
+        assert_eq!(5111, decoded.value);
+        assert_eq!("4ZQ", decoded.canonical);
+        assert!(decoded.corrections.is_empty());
+        assert_eq!(3, decoded.width);
+    }
+
+    #[test]
+    fn ambiguous_symbols_are_reported() {
+        let decoded = decode_diagnostic("IlOo").unwrap();
+        let found: Vec<_> = decoded.corrections.iter().map(|c| c.found).collect();
+
+        assert_eq!("1100", decoded.canonical);
+        assert_eq!(b"IlOo", &found[..]);
+        assert_eq!(
+            Correction {
+                index: 1,
+                found: b'l',
+                canonical: b'1',
+            },
+            decoded.corrections[1]
+        );
+    }
+
+    #[test]
+    fn unchecked_input_has_no_checksum() {
+        assert_eq!(None, decode_diagnostic("4zq5").unwrap().checksum);
+        assert_eq!(None, decode_diagnostic("7").unwrap().checksum);
+    }
+
+    #[test]
+    fn check_symbols_are_verified() {
+        // 5111 % 37 = 5, so 4ZQ5 ends in a valid check symbol.
+        let decoded = decode_diagnostic_with_check("4zq5").unwrap();
+        assert_eq!(Some(ChecksumStatus::Valid), decoded.checksum);
+        assert_eq!(
+            (5111, "4ZQ", 3),
+            (decoded.value, &*decoded.canonical, decoded.width)
+        );
+
+        assert_eq!(
+            Some(ChecksumStatus::Invalid { expected: b'5' }),
+            decode_diagnostic_with_check("4ZQ6").unwrap().checksum
+        );
+    }
+
+    #[test]
+    fn extra_check_symbols_are_accepted() {
+        // 32 through 36 call for *, ~, $, = and U.
+        for n in (32..37).chain((1 << 40) * 37 + 32..(1 << 40) * 37 + 37) {
+            let input = encode_with_check(n).to_lowercase();
+            let decoded = decode_diagnostic_with_check(&input).unwrap();
+
+            assert_eq!(Some(ChecksumStatus::Valid), decoded.checksum);
+            assert_eq!(n, decoded.value);
+            assert_eq!(encode(n), decoded.canonical);
+            assert_eq!(input.len() - 1, decoded.width);
+            assert!(decoded.corrections.is_empty());
+        }
+    }
+
+    #[test]
+    fn the_widest_checked_values_are_accepted() {
+        let input = encode_with_check(u64::MAX);
+        let decoded = decode_diagnostic_with_check(&input).unwrap();
+
+        assert_eq!(MAX_ENCODED_LEN_U64 + 1, input.len());
+        assert_eq!(u64::MAX, decoded.value);
+        assert_eq!(Some(ChecksumStatus::Valid), decoded.checksum);
+    }
+
+    #[test]
+    fn check_symbols_are_corrected() {
+        // 37 is 15 with a check symbol of 0.
+        let decoded = decode_diagnostic_with_check("15o").unwrap();
+
+        assert_eq!(Some(ChecksumStatus::Valid), decoded.checksum);
+        assert_eq!(
+            vec![Correction {
+                index: 2,
+                found: b'o',
+                canonical: b'0',
+            }],
+            decoded.corrections
+        );
+    }
+
+    #[test]
+    fn checked_input_fails_like_decode_with_check() {
+        for &input in &["", "5", "4ZQ!", "4Z!5", "4ZQ\u{e9}", "fzzzzzzzzzzzz01"] {
+            assert_eq!(
+                decode_with_check(input).map(|_| ()),
+                decode_diagnostic_with_check(input).map(|_| ())
+            );
+        }
+    }
+
+    #[test]
+    fn values_match_decode() {
+        for &input in &["0", "1zzz", "fzzzzzzzzzzzz", "0000000000001"] {
+            assert_eq!(
+                decode(input).unwrap(),
+                decode_diagnostic(input).unwrap().value
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_input_fails_like_decode() {
+        let expected = Err(Error::new(Kind::InvalidDigit(3, 33), "Don't care"));
+        assert_eq!(expected, decode_diagnostic("fZZ!2"));
+
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+        assert_eq!(expected, decode_diagnostic("g000000000000"));
+    }
+}
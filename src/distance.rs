@@ -0,0 +1,121 @@
+use core::{cmp, mem};
+
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{decoding::to_normal_digit, encode};
+
+/// Returns the number of single-symbol edits needed to turn one code into another.
+///
+/// Insertions, deletions and substitutions each count as one edit. Codes are compared the way
+/// they would be decoded, so case differences and ambiguous symbols such as `O` for `0` do not
+/// count as edits.
+///
+/// ```rust
+/// assert_eq!(0, crockford::edit_distance("4ZQ", "4zq"));
+/// assert_eq!(0, crockford::edit_distance("1O", "i0"));
+/// assert_eq!(1, crockford::edit_distance("4ZQ", "4Q"));
+/// assert_eq!(2, crockford::edit_distance("4ZQ", "Z4Q"));
+/// ```
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a = normalize(a);
+    let b = normalize(b);
+
+    // Classic two-row Levenshtein.
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, x) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(x != y);
+            current[j + 1] = cmp::min(substitution, cmp::min(previous[j + 1], current[j]) + 1);
+        }
+        mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Returns the encoding of the first candidate that is at least `min_distance` edits away from
+/// every existing code.
+///
+/// Candidates are typically random values or a counter. Returns `None` if the candidates run out
+/// before a distant enough one turns up.
+///
+/// ```rust
+/// let existing = ["4ZQ", "4ZR"];
+/// let code = crockford::generate_distant(&existing, 2, 5100..).unwrap();
+///
+/// assert_eq!("500", code);
+/// ```
+pub fn generate_distant<I>(existing: &[&str], min_distance: usize, candidates: I) -> Option<String>
+where
+    I: IntoIterator<Item = u64>,
+{
+    candidates.into_iter().map(encode).find(|code| {
+        existing
+            .iter()
+            .all(|other| edit_distance(code, other) >= min_distance)
+    })
+}
+
+/// Maps each symbol to its digit value, or to a value above any digit if it is not one.
+fn normalize(s: &str) -> Vec<u16> {
+    s.bytes()
+        .enumerate()
+        .map(|(idx, u)| match to_normal_digit(idx, u) {
+            Ok(digit) => u16::from(digit),
+            Err(_) => 0x100 | u16::from(u.to_ascii_uppercase()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{edit_distance, generate_distant};
+
+    #[test]
+    fn identical_codes_have_no_distance() {
+        assert_eq!(0, edit_distance("", ""));
+        assert_eq!(0, edit_distance("FZZZZZZZZZZZZ", "fzzzzzzzzzzzz"));
+    }
+
+    #[test]
+    fn empty_codes_are_as_far_as_they_are_long() {
+        assert_eq!(3, edit_distance("", "4ZQ"));
+        assert_eq!(3, edit_distance("4ZQ", ""));
+    }
+
+    #[test]
+    fn ambiguous_symbols_are_the_same() {
+        assert_eq!(0, edit_distance("LIO", "110"));
+    }
+
+    #[test]
+    fn invalid_symbols_still_compare() {
+        assert_eq!(0, edit_distance("4-Q", "4-q"));
+        assert_eq!(1, edit_distance("4-Q", "4!Q"));
+    }
+
+    #[test]
+    fn basic_edits_count_once() {
+        assert_eq!(1, edit_distance("4ZQ", "4ZQX"));
+        assert_eq!(1, edit_distance("4ZQ", "4XQ"));
+        assert_eq!(3, edit_distance("KITTEN", "SITTING"));
+    }
+
+    #[test]
+    fn distant_codes_are_distant() {
+        let existing = ["0000", "1111", "2222"];
+        let code = generate_distant(&existing, 4, 0..).unwrap();
+
+        assert!(existing
+            .iter()
+            .all(|other| edit_distance(&code, other) >= 4));
+    }
+
+    #[test]
+    fn exhausted_candidates_produce_nothing() {
+        assert_eq!(None, generate_distant(&["0"], 1, vec![0]));
+    }
+}
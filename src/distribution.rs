@@ -0,0 +1,79 @@
+use rand::{
+    distributions::{DistString, Distribution},
+    Rng,
+};
+
+use crate::UPPERCASE_ENCODING;
+
+/// Samples uniformly distributed Crockford Base32 symbols as ASCII bytes.
+///
+/// This works wherever `rand::distributions::Alphanumeric` does, except that every symbol is
+/// drawn from the uppercase Crockford alphabet.
+///
+/// ```rust
+/// use crockford::CrockfordAlphanumeric;
+/// use rand::Rng;
+///
+/// let code: String = rand::thread_rng()
+///     .sample_iter(CrockfordAlphanumeric)
+///     .take(10)
+///     .map(char::from)
+///     .collect();
+///
+/// assert_eq!(10, code.len());
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrockfordAlphanumeric;
+
+impl Distribution<u8> for CrockfordAlphanumeric {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        // The alphabet has exactly 32 symbols, so the top five bits pick one without bias.
+        UPPERCASE_ENCODING[(rng.next_u32() >> 27) as usize]
+    }
+}
+
+impl DistString for CrockfordAlphanumeric {
+    fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+        string.extend(self.sample_iter(rng).take(len).map(char::from));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{distributions::DistString, rngs::mock::StepRng, Rng};
+
+    use crate::{CrockfordAlphanumeric, UPPERCASE_ENCODING};
+
+    #[test]
+    fn samples_are_crockford_symbols() {
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..1000 {
+            assert!(UPPERCASE_ENCODING.contains(&rng.sample(CrockfordAlphanumeric)));
+        }
+    }
+
+    #[test]
+    fn every_symbol_is_reachable() {
+        // Step through the top five bits one symbol at a time.
+        let mut rng = StepRng::new(0, 1 << 27);
+        let symbols: Vec<u8> = (&mut rng)
+            .sample_iter(CrockfordAlphanumeric)
+            .take(32)
+            .collect();
+
+        assert_eq!(UPPERCASE_ENCODING, &symbols[..]);
+    }
+
+    #[test]
+    fn strings_have_requested_length() {
+        let s = CrockfordAlphanumeric.sample_string(&mut rand::thread_rng(), 26);
+
+        assert_eq!(26, s.len());
+        assert!(s.bytes().all(|u| UPPERCASE_ENCODING.contains(&u)));
+
+        // Random strings are arbitrary symbols, not encoded values; this one would overflow a u64.
+        let s = CrockfordAlphanumeric.sample_string(&mut StepRng::new(u64::MAX, 0), 26);
+        assert_eq!("Z".repeat(26), s);
+    }
+}
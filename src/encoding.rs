@@ -1,3 +1,19 @@
+use core::{fmt, str};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+use crate::{error::Kind, Error, Result};
+
+/// The longest possible encoding of a `u32` value, in digits.
+pub const MAX_ENCODED_LEN_U32: usize = 7;
+
+/// The longest possible encoding of a `u64` value, in digits.
+pub const MAX_ENCODED_LEN_U64: usize = 13;
+
+/// The longest possible encoding of a `u128` value, in digits.
+pub const MAX_ENCODED_LEN_U128: usize = 26;
+
 /// Represents writable buffer capable of receiving encoded data.
 ///
 /// Write is implemented on `Vec<u8>` and `String`, but you are free to implement it on your own
@@ -8,6 +24,7 @@ pub trait Write {
     fn write(&mut self, u: u8);
 }
 
+#[cfg(feature = "alloc")]
 impl Write for String {
     fn write(&mut self, u: u8) {
         // UPPERCASE_ENCODING contains only ASCII bytes.
@@ -17,25 +34,41 @@ impl Write for String {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Write for Vec<u8> {
     fn write(&mut self, u: u8) {
         self.push(u);
     }
 }
 
+#[cfg(feature = "alloc")]
 /// Encodes a `u64` value as a Crockford Base32-encoded string.
 pub fn encode(n: u64) -> String {
-    // The longest possible representation of u64 in Base32 is 13 digits.
-    let mut fits = Vec::with_capacity(13);
+    let mut fits = Vec::with_capacity(MAX_ENCODED_LEN_U64);
     encode_into(n, &mut fits);
 
     // UPPERCASE_ENCODING contains only ASCII bytes.
     unsafe { String::from_utf8_unchecked(fits) }
 }
 
+/// Returns the number of digits produced by encoding the provided `u64` value.
+///
+/// Smaller integer types encode exactly as their `u64` equivalents, so pass those through
+/// `u64::from` first. This is useful for sizing a buffer before calling `encode_into`;
+/// `encoded_int_len` does the same for `u128`, and `encoded_bytes_len` for byte slices.
+pub const fn encoded_len(n: u64) -> usize {
+    // Every digit carries five bits; zero still takes up one digit.
+    let bits = 64 - n.leading_zeros() as usize;
+    if bits == 0 {
+        1
+    } else {
+        bits.div_ceil(5)
+    }
+}
+
 /// Encodes a `u64` value as Crockford Base32 and writes it to the provided output.
 ///
-/// Either `String` or `Vec<u8>` will be accepted.
+/// Either `String` or `Vec<u8>` will be accepted, as will any other implementation of `Write`.
 pub fn encode_into<T: Write>(mut n: u64, w: &mut T) {
     use crate::UPPERCASE_ENCODING;
 
@@ -85,11 +118,349 @@ pub fn encode_into<T: Write>(mut n: u64, w: &mut T) {
     }
 }
 
-#[cfg(test)]
+/// Encodes a `u64` value into the provided stack buffer and returns the encoded digits.
+///
+/// This needs no allocator, which makes it the simplest way to encode values in `no_std` code.
+///
+/// ```rust
+/// let mut buf = [0; crockford::MAX_ENCODED_LEN_U64];
+///
+/// assert_eq!("4ZQ", crockford::encode_to_array(5111, &mut buf));
+/// ```
+pub fn encode_to_array(n: u64, buf: &mut [u8; MAX_ENCODED_LEN_U64]) -> &str {
+    let mut digits = Digits::<MAX_ENCODED_LEN_U64>::new();
+    encode_into(n, &mut digits);
+
+    let len = digits.len;
+    buf[..len].copy_from_slice(&digits.buf[..len]);
+
+    // UPPERCASE_ENCODING contains only ASCII bytes.
+    unsafe { str::from_utf8_unchecked(&buf[..len]) }
+}
+
+/// Encodes a `u64` value at compile time.
+///
+/// This produces exactly what `encode` does, but can be evaluated in a constant. The
+/// `encode_const!` macro goes one step further and gives the digits as a `&'static str`.
+///
+/// ```rust
+/// const ADMIN: crockford::Encoded = crockford::encode_const(5111);
+/// const ADMIN_ID: &str = crockford::encode_const!(5111);
+///
+/// assert_eq!("4ZQ", ADMIN.as_str());
+/// assert_eq!("4ZQ", ADMIN_ID);
+/// ```
+pub const fn encode_const(n: u64) -> Encoded {
+    use crate::UPPERCASE_ENCODING;
+
+    let len = encoded_len(n);
+    let mut buf = [0; MAX_ENCODED_LEN_U64];
+    let mut rest = n;
+    let mut idx = len;
+    while idx > 0 {
+        idx -= 1;
+        buf[idx] = UPPERCASE_ENCODING[(rest & 0x1F) as usize];
+        rest >>= 5;
+    }
+
+    Encoded { buf, len }
+}
+
+/// Encodes a `u64` constant into a `&'static str` at compile time.
+///
+/// The argument must itself be a constant expression. See `encode_const`.
+#[macro_export]
+macro_rules! encode_const {
+    ($n:expr) => {{
+        const ENCODED: $crate::Encoded = $crate::encode_const($n);
+        const DIGITS: &str = $crate::Encoded::as_str(&ENCODED);
+        DIGITS
+    }};
+}
+
+/// The digits of a value encoded by `encode_const`, held inline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Encoded {
+    buf: [u8; MAX_ENCODED_LEN_U64],
+    len: usize,
+}
+
+impl Encoded {
+    /// Returns the encoded digits.
+    pub const fn as_str(&self) -> &str {
+        // UPPERCASE_ENCODING contains only ASCII bytes.
+        unsafe { str::from_utf8_unchecked(self.buf.split_at(self.len).0) }
+    }
+}
+
+impl fmt::Display for Encoded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Encodes an `i64` value as a sign followed by the Crockford Base32 encoding of its magnitude.
+///
+/// Negative values get a leading `-`; other values are encoded exactly as `encode` would encode
+/// them.
+///
+/// ```rust
+/// assert_eq!("-4ZQ", crockford::encode_signed_display(-5111));
+/// assert_eq!("4ZQ", crockford::encode_signed_display(5111));
+/// ```
+pub fn encode_signed_display(n: i64) -> String {
+    let mut s = String::with_capacity(MAX_ENCODED_LEN_U64 + 1);
+    if n < 0 {
+        s.push('-');
+    }
+    encode_into(n.unsigned_abs(), &mut s);
+    s
+}
+
+#[cfg(feature = "alloc")]
+/// Encodes each value and joins the results with the provided separator.
+///
+/// The output is allocated once, sized from the iterator's lower size hint, so passing a slice
+/// iterator or a `Vec` avoids reallocating as the string grows.
+///
+/// ```rust
+/// let ids = vec![5111, 1, 32];
+///
+/// assert_eq!("4ZQ,1,10", crockford::encode_joined(ids, ','));
+/// assert_eq!("", crockford::encode_joined(None, ','));
+/// ```
+pub fn encode_joined<I: IntoIterator<Item = u64>>(values: I, sep: char) -> String {
+    let values = values.into_iter();
+    let (count, _) = values.size_hint();
+    let mut s = String::with_capacity(count * (MAX_ENCODED_LEN_U64 + sep.len_utf8()));
+
+    for (idx, n) in values.enumerate() {
+        if idx > 0 {
+            s.push(sep);
+        }
+        encode_into(n, &mut s);
+    }
+
+    s
+}
+
+#[cfg(feature = "alloc")]
+/// Encodes every value in a slice, appending them to the provided string with the separator
+/// between them.
+///
+/// This is `encode_joined` for bulk work. The exact output length is worked out up front so the
+/// string grows at most once, and each value is written with a fixed number of steps and no
+/// branches on its digits, which lets the compiler unroll the inner loop.
+///
+/// ```rust
+/// let mut s = String::from("ids: ");
+/// crockford::encode_slice(&[5111, 1, 32], ',', &mut s);
+///
+/// assert_eq!("ids: 4ZQ,1,10", s);
+/// ```
+pub fn encode_slice(values: &[u64], sep: char, out: &mut String) {
+    let mut sep_buf = [0; 4];
+    let sep = sep.encode_utf8(&mut sep_buf).as_bytes();
+    let digits: usize = values.iter().map(|&n| encoded_len(n)).sum();
+    let len = digits + sep.len() * values.len().saturating_sub(1);
+
+    // Only ASCII digits and whole separators are written over the zeroed bytes, so the string
+    // stays valid UTF-8 even if this panics partway through.
+    let buf = unsafe { out.as_mut_vec() };
+    let mut pos = buf.len();
+    buf.resize(pos + len, 0);
+
+    for (idx, &n) in values.iter().enumerate() {
+        if idx > 0 {
+            buf[pos..pos + sep.len()].copy_from_slice(sep);
+            pos += sep.len();
+        }
+
+        // Counting leading zeros again is cheaper than keeping every length around.
+        let len = encoded_len(n);
+        buf[pos..pos + len].copy_from_slice(&all_digits(n)[MAX_ENCODED_LEN_U64 - len..]);
+        pos += len;
+    }
+}
+
+#[cfg(feature = "alloc")]
+/// Encodes a `u64` value as exactly `MAX_ENCODED_LEN_U64` digits, leading zeros included.
+fn all_digits(n: u64) -> [u8; MAX_ENCODED_LEN_U64] {
+    use crate::UPPERCASE_ENCODING;
+
+    let mut digits = [0; MAX_ENCODED_LEN_U64];
+    for (place, digit) in digits.iter_mut().rev().enumerate() {
+        *digit = UPPERCASE_ENCODING[(n >> (place * 5) & 0x1F) as usize];
+    }
+    digits
+}
+
+#[cfg(feature = "alloc")]
+/// Encodes a `u64` value with a separator between every `group_size` digits.
+///
+/// Groups are counted from the most significant digit, so only the last group may be short.
+/// The spec says hyphens are to be ignored when decoding, which makes `-` the usual separator;
+/// `decode_grouped` reads the result back.
+///
+/// # Panics
+///
+/// Panics if `group_size` is zero.
+///
+/// ```rust
+/// assert_eq!("13XR-ZP16-B", crockford::encode_grouped(1234567890123, 4, '-'));
+/// assert_eq!("4ZQ", crockford::encode_grouped(5111, 4, '-'));
+/// ```
+pub fn encode_grouped(n: u64, group_size: usize, separator: char) -> String {
+    let mut digits = Digits::<MAX_ENCODED_LEN_U64>::new();
+    encode_into(n, &mut digits);
+    group_encoded(digits.as_str(), group_size, separator)
+}
+
+#[cfg(feature = "alloc")]
+/// Separates every `group_size` symbols of an already-encoded string with a separator.
+///
+/// This is how `encode_grouped` groups its output, for strings that come from elsewhere, such as
+/// `encode_with_check`. Groups are counted from the start of the string.
+///
+/// # Panics
+///
+/// Panics if `group_size` is zero.
+///
+/// ```rust
+/// let encoded = crockford::encode_with_check(1234567890123);
+/// assert_eq!("13XR-ZP16-B2", crockford::group_encoded(&encoded, 4, '-'));
+/// ```
+pub fn group_encoded(encoded: &str, group_size: usize, separator: char) -> String {
+    assert!(group_size > 0, "group size must be nonzero");
+
+    let symbols = encoded.chars().count();
+    let separators = symbols.saturating_sub(1) / group_size;
+    let mut s = String::with_capacity(encoded.len() + separators * separator.len_utf8());
+    for (idx, c) in encoded.chars().enumerate() {
+        if idx > 0 && idx % group_size == 0 {
+            s.push(separator);
+        }
+        s.push(c);
+    }
+
+    s
+}
+
+#[cfg(feature = "alloc")]
+/// Encodes a `u64` value as exactly `width` digits, padding with leading zeros.
+///
+/// Fixed-width encodings sort the same way as the values they encode, which makes them suitable
+/// for sortable identifiers; a width of `MAX_ENCODED_LEN_U64` fits every value. Values are never
+/// truncated: if the value needs more digits than `width`, this fails with `OutOfRange`.
+///
+/// ```rust
+/// # use crockford::Error;
+/// # fn run() -> Result<(), Error> {
+/// assert_eq!("00000000004ZQ", crockford::encode_fixed(5111, 13)?);
+/// assert!(crockford::encode_fixed(5111, 2).is_err());
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+pub fn encode_fixed(n: u64, width: usize) -> Result<String> {
+    let mut s = String::with_capacity(width);
+    encode_fixed_into(n, width, &mut s)?;
+    Ok(s)
+}
+
+/// Encodes a `u64` value as exactly `width` digits and writes it to the provided output.
+///
+/// Nothing is written if the value does not fit. See `encode_fixed`.
+pub fn encode_fixed_into<T: Write>(n: u64, width: usize, w: &mut T) -> Result<()> {
+    if encoded_len(n) > width {
+        return Err(Error::new(
+            Kind::OutOfRange,
+            "Value does not fit in the requested width.",
+        ));
+    }
+
+    encode_padded(n.into(), width, w);
+    Ok(())
+}
+
+/// Returns a value that encodes `n` when it is displayed.
+///
+/// Nothing is encoded or allocated until the value is actually formatted, which makes this a
+/// good fit for log statements that may be filtered out. Width and alignment flags are honored.
+///
+/// ```rust
+/// let message = format!("user {:>5}", crockford::display(5111));
+///
+/// assert_eq!("user   4ZQ", message);
+/// ```
+pub fn display(n: u64) -> impl fmt::Display {
+    Display(n)
+}
+
+struct Display(u64);
+
+impl fmt::Display for Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut digits = Digits::<MAX_ENCODED_LEN_U64>::new();
+        encode_into(self.0, &mut digits);
+        f.pad(digits.as_str())
+    }
+}
+
+/// Stack buffer for up to `N` encoded digits.
+pub(crate) struct Digits<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Digits<N> {
+    pub(crate) const fn new() -> Self {
+        Digits {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        // UPPERCASE_ENCODING contains only ASCII bytes.
+        unsafe { str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+}
+
+impl<const N: usize> Write for Digits<N> {
+    fn write(&mut self, u: u8) {
+        self.buf[self.len] = u;
+        self.len += 1;
+    }
+}
+
+/// Encodes a value as exactly `width` digits, padding with leading zeros.
+///
+/// The width must be large enough to hold the encoded value.
+pub(crate) fn encode_padded<T: Write>(n: u128, width: usize, w: &mut T) {
+    use crate::UPPERCASE_ENCODING;
+
+    debug_assert!(width * 5 >= 128 - n.leading_zeros() as usize);
+
+    for place in (0..width).rev() {
+        let shift = place * 5;
+        let digit = if shift < 128 { (n >> shift) & 0x1F } else { 0 };
+        w.write(UPPERCASE_ENCODING[digit as usize]);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use std::str;
 
-    use crate::{decode, encode, encode_into};
+    use super::encode_padded;
+    use crate::{
+        decode, decode_signed_display, display, encode, encode_const, encode_fixed,
+        encode_fixed_into, encode_grouped, encode_into, encode_joined, encode_signed_display,
+        encode_slice, encode_to_array, encoded_len, error::Kind, group_encoded, Error,
+        MAX_ENCODED_LEN_U32, MAX_ENCODED_LEN_U64,
+    };
 
     #[test]
     fn zero_returns_zero() {
@@ -145,6 +516,102 @@ mod tests {
         assert_eq!(x, y);
     }
 
+    #[test]
+    fn encoded_len_matches_encode() {
+        for &n in &[0, 1, 31, 32, 5111, 65535, 1 << 60, u64::MAX] {
+            assert_eq!(encode(n).len(), encoded_len(n));
+        }
+    }
+
+    #[test]
+    fn encoded_len_of_small_types() {
+        assert_eq!(2, encoded_len(u64::from(u8::MAX)));
+        assert_eq!(4, encoded_len(u64::from(u16::MAX)));
+        assert_eq!(7, encoded_len(u64::from(u32::MAX)));
+    }
+
+    #[test]
+    fn max_lengths_match_max_values() {
+        assert_eq!(MAX_ENCODED_LEN_U32, encode(u64::from(u32::MAX)).len());
+        assert_eq!(MAX_ENCODED_LEN_U64, encode(u64::MAX).len());
+    }
+
+    #[test]
+    fn padded_values_have_leading_zeros() {
+        let mut s = String::new();
+        encode_padded(5111, 7, &mut s);
+        assert_eq!("00004ZQ", s);
+
+        s.clear();
+        encode_padded(u64::MAX.into(), 13, &mut s);
+        assert_eq!("FZZZZZZZZZZZZ", s);
+
+        s.clear();
+        encode_padded(u128::MAX, 26, &mut s);
+        assert_eq!("7ZZZZZZZZZZZZZZZZZZZZZZZZZ", s);
+    }
+
+    #[test]
+    fn display_matches_encode() {
+        for &n in &[0, 5111, u64::MAX] {
+            assert_eq!(encode(n), display(n).to_string());
+        }
+    }
+
+    #[test]
+    fn signed_display_round_trips() {
+        for &n in &[0, 1, -1, 5111, -5111, i64::MAX, i64::MIN] {
+            assert_eq!(Ok(n), decode_signed_display(encode_signed_display(n)));
+        }
+    }
+
+    #[test]
+    fn signed_display_extremes() {
+        assert_eq!("7ZZZZZZZZZZZZ", encode_signed_display(i64::MAX));
+        assert_eq!("-8000000000000", encode_signed_display(i64::MIN));
+    }
+
+    #[test]
+    fn joined_values_have_one_separator_between_each() {
+        assert_eq!("", encode_joined(vec![], ','));
+        assert_eq!("4ZQ", encode_joined(vec![5111], ','));
+        assert_eq!(
+            "0 ZZ 0 FZZZZZZZZZZZZ",
+            encode_joined(vec![0, 1023, 0, u64::MAX], ' ')
+        );
+    }
+
+    #[test]
+    fn joined_values_reserve_enough_capacity() {
+        let joined = encode_joined(vec![u64::MAX; 3], '→');
+        assert_eq!(3 * 13 + 2 * '→'.len_utf8(), joined.len());
+        assert!(joined.capacity() >= joined.len());
+    }
+
+    #[test]
+    fn slices_encode_like_joined_values() {
+        let values: Vec<u64> = (0..64)
+            .map(|shift| (1 << shift) - 1)
+            .chain(Some(5111))
+            .collect();
+
+        for &sep in &[',', '→'] {
+            let mut s = String::new();
+            encode_slice(&values, sep, &mut s);
+            assert_eq!(encode_joined(values.iter().copied(), sep), s);
+        }
+    }
+
+    #[test]
+    fn slices_append_to_the_string() {
+        let mut s = String::from("ids:");
+        encode_slice(&[], ',', &mut s);
+        encode_slice(&[0], ',', &mut s);
+        encode_slice(&[u64::MAX, 32], ' ', &mut s);
+
+        assert_eq!("ids:0FZZZZZZZZZZZZ 10", s);
+    }
+
     // Test is ignored because it takes forever to run.
     #[ignore]
     #[test]
@@ -157,10 +624,90 @@ mod tests {
             s.clear();
         }
 
-        for n in (u64::max_value() - 20_000_000)..u64::max_value() {
+        for n in (u64::MAX - 20_000_000)..u64::MAX {
             encode_into(n, &mut s);
             assert_eq!(n, decode(str::from_utf8(&s).unwrap()).unwrap());
             s.clear();
         }
     }
+
+    #[test]
+    fn grouping_counts_from_the_most_significant_digit() {
+        assert_eq!("13XR-ZP16-B", encode_grouped(1234567890123, 4, '-'));
+        assert_eq!("13XR ZP16 B", encode_grouped(1234567890123, 4, ' '));
+        assert_eq!("13XRZP16B", encode_grouped(1234567890123, 9, '-'));
+        assert_eq!("0", encode_grouped(0, 1, '-'));
+        assert_eq!("FZZ–ZZZ–ZZZ–ZZZ–Z", encode_grouped(u64::MAX, 3, '–'));
+    }
+
+    #[test]
+    #[should_panic]
+    fn grouping_by_zero_panics() {
+        encode_grouped(5111, 0, '-');
+    }
+
+    #[test]
+    fn grouping_encoded_strings_counts_symbols() {
+        assert_eq!("4ZQ-5", group_encoded("4ZQ5", 3, '-'));
+        assert_eq!("4ZQ5", group_encoded("4ZQ5", 4, '-'));
+        assert_eq!("", group_encoded("", 2, '-'));
+        assert_eq!("4Z–Q5", group_encoded("4ZQ5", 2, '–'));
+    }
+
+    #[test]
+    fn fixed_width_pads_with_zeros() {
+        assert_eq!(Ok("00000000004ZQ".to_owned()), encode_fixed(5111, 13));
+        assert_eq!(Ok("4ZQ".to_owned()), encode_fixed(5111, 3));
+        assert_eq!(Ok("0".to_owned()), encode_fixed(0, 1));
+        assert_eq!(
+            Ok("0".repeat(7) + "FZZZZZZZZZZZZ"),
+            encode_fixed(u64::MAX, 20)
+        );
+    }
+
+    #[test]
+    fn fixed_width_never_truncates() {
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+        assert_eq!(expected, encode_fixed(5111, 2));
+        assert_eq!(expected, encode_fixed(0, 0));
+
+        let mut s = String::from("id:");
+        assert_eq!(expected.map(|_| ()), encode_fixed_into(5111, 2, &mut s));
+        assert_eq!("id:", s);
+    }
+
+    #[test]
+    fn fixed_width_sorts_like_values() {
+        let mut values = vec![5111, 1, 32, u64::MAX, 0, 31];
+        let mut encoded: Vec<_> = values
+            .iter()
+            .map(|&n| encode_fixed(n, 13).unwrap())
+            .collect();
+
+        values.sort_unstable();
+        encoded.sort_unstable();
+        let decoded: Vec<_> = encoded.iter().map(|s| decode(s).unwrap()).collect();
+        assert_eq!(values, decoded);
+    }
+
+    #[test]
+    fn array_encoding_matches_encode() {
+        let mut buf = [0; MAX_ENCODED_LEN_U64];
+        for &n in &[0, 1, 5111, u64::MAX] {
+            assert_eq!(encode(n), encode_to_array(n, &mut buf));
+        }
+    }
+
+    #[test]
+    fn const_encoding_matches_encode() {
+        const ZERO: &str = crate::encode_const!(0);
+        const MAX: &str = crate::encode_const!(u64::MAX);
+
+        assert_eq!("0", ZERO);
+        assert_eq!("FZZZZZZZZZZZZ", MAX);
+        for &n in &[1, 31, 32, 5111, 1 << 63] {
+            assert_eq!(encode(n), encode_const(n).as_str());
+            assert_eq!(encode(n), encode_const(n).to_string());
+        }
+    }
 }
@@ -1,39 +1,195 @@
-use std::{error, fmt};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::{error, io};
 
 /// Represents an error in decoding.
 #[derive(Debug)]
 pub struct Error {
     kind: Kind,
     message: &'static str,
+    element: Option<usize>,
 }
 
 impl Error {
-    pub(crate) fn new(kind: Kind, message: &'static str) -> Error {
-        Error { kind, message }
+    pub(crate) const fn new(kind: Kind, message: &'static str) -> Error {
+        Error {
+            kind,
+            message,
+            element: None,
+        }
+    }
+
+    /// Returns the position of the offending element, when decoding a list of values.
+    ///
+    /// Byte indexes reported alongside are relative to the start of that element.
+    pub fn element(&self) -> Option<usize> {
+        self.element
+    }
+
+    pub(crate) fn in_element(mut self, element: usize) -> Error {
+        self.element = Some(element);
+        self
+    }
+
+    /// Returns what kind of error this is.
+    pub fn kind(&self) -> &Kind {
+        &self.kind
     }
+
+    /// Returns the index of the offending byte, if the error concerns one.
+    pub fn index(&self) -> Option<usize> {
+        match self.kind {
+            Kind::InvalidDigit(idx, _) | Kind::CheckDigitUnsupported(idx, _) => Some(idx),
+            _ => None,
+        }
+    }
+
+    /// Returns the offending byte, if the error concerns one.
+    ///
+    /// This is a single byte of the input, so for a non-ASCII character it is only the first byte
+    /// of its UTF-8 encoding.
+    pub fn byte(&self) -> Option<u8> {
+        match self.kind {
+            Kind::InvalidDigit(_, u) | Kind::CheckDigitUnsupported(_, u) => Some(u),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+/// Represents an error in decoding, along with where in the input it happened.
+///
+/// Lines and columns are both counted from one, and columns are counted in bytes. When reading
+/// from an I/O source, the error may instead be an I/O error, in which case the column is zero.
+#[derive(Debug)]
+pub struct LineError {
+    line: usize,
+    column: usize,
+    cause: Cause,
 }
 
+#[cfg(feature = "std")]
 #[derive(Debug)]
+enum Cause {
+    Decode(Error),
+    Io(io::Error),
+}
+
+#[cfg(feature = "std")]
+impl LineError {
+    pub(crate) fn new(line: usize, column: usize, error: Error) -> LineError {
+        LineError {
+            line,
+            column,
+            cause: Cause::Decode(error),
+        }
+    }
+
+    pub(crate) fn io(line: usize, error: io::Error) -> LineError {
+        LineError {
+            line,
+            column: 0,
+            cause: Cause::Io(error),
+        }
+    }
+
+    /// Returns the line on which the error happened.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column at which the error happened.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the underlying decoding error, if this is one.
+    pub fn error(&self) -> Option<&Error> {
+        match &self.cause {
+            Cause::Decode(e) => Some(e),
+            Cause::Io(_) => None,
+        }
+    }
+
+    /// Returns the underlying I/O error, if this is one.
+    pub fn io_error(&self) -> Option<&io::Error> {
+        match &self.cause {
+            Cause::Decode(_) => None,
+            Cause::Io(e) => Some(e),
+        }
+    }
+
+    /// Moves the error down by the provided number of lines.
+    pub(crate) fn offset_lines(mut self, lines: usize) -> LineError {
+        self.line += lines;
+        self
+    }
+}
+
+/// Describes what went wrong in decoding.
+///
+/// Variants concerning a single byte carry its index in the input and its value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Kind {
+    /// A check symbol (`*~$=U`) appeared where a digit was expected.
     CheckDigitUnsupported(usize, u8),
+    /// The input held no digits.
     EmptyString,
+    /// A check symbol did not match the value it follows.
+    InvalidChecksum,
+    /// A byte is not a Crockford digit.
     InvalidDigit(usize, u8),
+    /// The input does not have the expected structure, such as a missing prefix or separator.
+    InvalidFormat,
+    /// The input has the wrong number of digits or bytes.
+    InvalidLength,
+    #[cfg(feature = "signed")]
+    /// A signature did not match the value it signs.
+    InvalidSignature,
+    /// The encoded value does not fit in the target type.
     OutOfRange,
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.kind {
-            Kind::InvalidDigit(idx, digit) => {
-                write!(f, "{} Byte value {} at index {}", self.message, digit, idx)
+            Kind::InvalidDigit(idx, digit) | Kind::CheckDigitUnsupported(idx, digit) => {
+                write!(f, "{} Byte value {} at index {}", self.message, digit, idx)?
             }
-            _ => write!(f, "{}", self.message),
+            _ => write!(f, "{}", self.message)?,
+        }
+
+        match self.element {
+            Some(element) => write!(f, " (element {})", element),
+            None => Ok(()),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl error::Error for Error {}
 
+#[cfg(feature = "std")]
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.cause {
+            Cause::Decode(e) => write!(f, "Line {}, column {}: {}", self.line, self.column, e),
+            Cause::Io(e) => write!(f, "Line {}: {}", self.line, e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for LineError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.cause {
+            Cause::Decode(e) => Some(e),
+            Cause::Io(e) => Some(e),
+        }
+    }
+}
+
 #[cfg(test)]
 impl PartialEq for Error {
     fn eq(&self, other: &Error) -> bool {
@@ -43,9 +199,26 @@ impl PartialEq for Error {
                 Kind::OutOfRange => 2,
                 Kind::InvalidDigit(..) => 3,
                 Kind::CheckDigitUnsupported(..) => 4,
+                Kind::InvalidLength => 5,
+                #[cfg(feature = "signed")]
+                Kind::InvalidSignature => 6,
+                Kind::InvalidChecksum => 7,
+                Kind::InvalidFormat => 8,
             }
         }
 
         kind_value(&self.kind) == kind_value(&other.kind)
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+impl PartialEq for LineError {
+    fn eq(&self, other: &LineError) -> bool {
+        let same_cause = match (&self.cause, &other.cause) {
+            (Cause::Decode(a), Cause::Decode(b)) => a == b,
+            (Cause::Io(a), Cause::Io(b)) => a.kind() == b.kind(),
+            _ => false,
+        };
+        self.line == other.line && self.column == other.column && same_cause
+    }
+}
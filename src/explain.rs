@@ -0,0 +1,357 @@
+use alloc::vec::Vec;
+
+use crate::{
+    check::{check_symbol, CHECK_SYMBOLS},
+    decoding::to_normal_digit,
+    UPPERCASE_ENCODING,
+};
+
+/// A symbol-by-symbol account of how a code is read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    /// Every symbol in the input, in order.
+    pub symbols: Vec<Symbol>,
+
+    /// The value of all the digits taken together, if they are all valid and fit in a `u64`.
+    ///
+    /// Hyphens are skipped, so this is the value `decode_lenient` returns. `decode` rejects
+    /// hyphens outright; see `hyphenated`.
+    pub value: Option<u64>,
+
+    /// Whether the input contains any hyphens, which `decode` would reject.
+    pub hyphenated: bool,
+
+    /// The check symbol math, if the final symbol were a check symbol over the digits before it.
+    ///
+    /// Nothing in a code says whether it ends in a check symbol, so this is offered whenever the
+    /// code has at least two symbols that could be read that way.
+    pub check: Option<Check>,
+}
+
+/// A single symbol in the input and how it was read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    /// The index of the symbol in the input, in bytes.
+    pub index: usize,
+
+    /// The symbol as it appeared in the input.
+    pub found: char,
+
+    /// How the symbol was read.
+    pub reading: Reading,
+}
+
+/// How a symbol was read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reading {
+    /// An ordinary digit.
+    Digit {
+        /// The value of the digit.
+        value: u8,
+
+        /// The canonical uppercase symbol for the digit, which differs from the input if it was
+        /// lowercase or one of the ambiguous symbols `O`, `I` and `L`.
+        canonical: u8,
+
+        /// How far the digit is shifted within the value; it contributes `value << shift`.
+        shift: u32,
+
+        /// The value of the digits up to and including this one, if it fits in a `u64`.
+        accumulated: Option<u64>,
+    },
+
+    /// One of the five symbols that may only appear as a check symbol.
+    CheckOnly {
+        /// The value of the check symbol, from 32 to 36.
+        value: u8,
+    },
+
+    /// A hyphen, which the spec says to ignore.
+    ///
+    /// `decode_lenient` skips hyphens as the spec asks, but `decode` rejects them.
+    Separator,
+
+    /// A symbol that is not part of the encoding at all.
+    Invalid,
+}
+
+/// The check symbol math for a code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Check {
+    /// The index of the check symbol in the input, in bytes.
+    pub index: usize,
+
+    /// The canonical form of the check symbol found in the input.
+    pub found: u8,
+
+    /// The value of the digits before the check symbol, modulo 37.
+    pub remainder: u8,
+
+    /// The check symbol those digits call for.
+    pub expected: u8,
+
+    /// Whether the found and expected check symbols agree.
+    pub matches: bool,
+}
+
+/// Explains how a code is read, symbol by symbol.
+///
+/// Unlike `decode`, this never fails: invalid symbols are reported as such, and whatever can be
+/// worked out from the rest of the code still is. Hyphens are read the way `decode_lenient`
+/// reads them, as separators to skip.
+///
+/// ```rust
+/// use crockford::Reading;
+///
+/// let explanation = crockford::explain("4zQ-5");
+///
+/// assert_eq!(5, explanation.symbols.len());
+/// assert_eq!(Reading::Separator, explanation.symbols[3].reading);
+/// assert_eq!(Some(163_557), explanation.value);
+/// assert!(explanation.hyphenated);
+///
+/// // 4ZQ is 5111, and 5111 % 37 is 5.
+/// let check = explanation.check.unwrap();
+/// assert_eq!((5, b'5', true), (check.remainder, check.expected, check.matches));
+/// ```
+pub fn explain(input: &str) -> Explanation {
+    let readings: Vec<_> = input
+        .char_indices()
+        .map(|(index, found)| (index, found, read(found)))
+        .collect();
+    let digit_count = readings
+        .iter()
+        .filter(|(_, _, r)| matches!(r, Reading::Digit { .. }))
+        .count();
+
+    let mut symbols = Vec::with_capacity(readings.len());
+    let mut accumulated = Some(0u64);
+    let mut remaining = digit_count as u32;
+
+    for (index, found, reading) in readings {
+        let reading = match reading {
+            Reading::Digit {
+                value, canonical, ..
+            } => {
+                remaining -= 1;
+                accumulated = accumulated
+                    .filter(|n| n.leading_zeros() >= 5)
+                    .map(|n| n << 5 | u64::from(value));
+                Reading::Digit {
+                    value,
+                    canonical,
+                    shift: remaining * 5,
+                    accumulated,
+                }
+            }
+            other => other,
+        };
+        symbols.push(Symbol {
+            index,
+            found,
+            reading,
+        });
+    }
+
+    let all_valid = symbols.iter().all(|symbol| match symbol.reading {
+        Reading::Digit { .. } | Reading::Separator => true,
+        Reading::CheckOnly { .. } | Reading::Invalid => false,
+    });
+    let value = accumulated.filter(|_| all_valid && digit_count > 0);
+    let hyphenated = symbols
+        .iter()
+        .any(|symbol| symbol.reading == Reading::Separator);
+
+    Explanation {
+        check: explain_check(&symbols),
+        symbols,
+        value,
+        hyphenated,
+    }
+}
+
+fn read(found: char) -> Reading {
+    if !found.is_ascii() {
+        return Reading::Invalid;
+    }
+    let u = found as u8;
+
+    match to_normal_digit(0, u) {
+        Ok(value) => Reading::Digit {
+            value,
+            canonical: UPPERCASE_ENCODING[usize::from(value)],
+            shift: 0,
+            accumulated: None,
+        },
+        Err(_) if u == b'-' => Reading::Separator,
+        Err(_) => match CHECK_SYMBOLS
+            .iter()
+            .position(|&c| c == u.to_ascii_uppercase())
+        {
+            Some(value) => Reading::CheckOnly { value: value as u8 },
+            None => Reading::Invalid,
+        },
+    }
+}
+
+fn explain_check(symbols: &[Symbol]) -> Option<Check> {
+    let mut significant = symbols
+        .iter()
+        .filter(|symbol| symbol.reading != Reading::Separator);
+    let last = significant.next_back()?;
+
+    let found = match last.reading {
+        Reading::Digit { canonical, .. } => canonical,
+        Reading::CheckOnly { value } => CHECK_SYMBOLS[usize::from(value)],
+        _ => return None,
+    };
+
+    let mut digits = Vec::new();
+    for symbol in significant {
+        match symbol.reading {
+            Reading::Digit { value, .. } => digits.push(value),
+            _ => return None,
+        }
+    }
+    if digits.is_empty() {
+        return None;
+    }
+
+    let expected = check_symbol(digits);
+    let remainder = CHECK_SYMBOLS
+        .iter()
+        .position(|&c| c == expected)
+        .expect("check symbols come from the table") as u8;
+
+    Some(Check {
+        index: last.index,
+        found,
+        remainder,
+        expected,
+        matches: found == expected,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{decode, decode_lenient, explain, Reading};
+
+    #[test]
+    fn digits_report_value_shift_and_running_total() {
+        let explanation = explain("4ZQ");
+        let readings: Vec<_> = explanation.symbols.iter().map(|s| s.reading).collect();
+
+        assert_eq!(
+            vec![
+                Reading::Digit {
+                    value: 4,
+                    canonical: b'4',
+                    shift: 10,
+                    accumulated: Some(4),
+                },
+                Reading::Digit {
+                    value: 31,
+                    canonical: b'Z',
+                    shift: 5,
+                    accumulated: Some(159),
+                },
+                Reading::Digit {
+                    value: 23,
+                    canonical: b'Q',
+                    shift: 0,
+                    accumulated: Some(5111),
+                },
+            ],
+            readings
+        );
+        assert_eq!(Some(5111), explanation.value);
+    }
+
+    #[test]
+    fn normalization_shows_in_the_canonical_symbol() {
+        let explanation = explain("oIl");
+        let canonical: Vec<_> = explanation
+            .symbols
+            .iter()
+            .map(|s| match s.reading {
+                Reading::Digit { canonical, .. } => canonical,
+                _ => panic!("not a digit"),
+            })
+            .collect();
+
+        assert_eq!(b"011".to_vec(), canonical);
+        assert_eq!(Some(33), explanation.value);
+    }
+
+    #[test]
+    fn bad_symbols_are_reported_without_failing() {
+        let explanation = explain("4!é~");
+
+        assert_eq!(Reading::Invalid, explanation.symbols[1].reading);
+        assert_eq!(Reading::Invalid, explanation.symbols[2].reading);
+        assert_eq!(4, explanation.symbols[3].index);
+        assert_eq!(
+            Reading::CheckOnly { value: 33 },
+            explanation.symbols[3].reading
+        );
+        assert_eq!(None, explanation.value);
+        assert_eq!(None, explanation.check);
+    }
+
+    #[test]
+    fn check_only_symbols_are_checked() {
+        // 14 is 36, and 36 % 37 is 36, which is U.
+        let explanation = explain("14u");
+        let check = explanation.check.unwrap();
+
+        assert_eq!(None, explanation.value);
+        assert_eq!(
+            (2, b'U', 36, true),
+            (check.index, check.found, check.remainder, check.matches)
+        );
+
+        let check = explain("14-*").check.unwrap();
+        assert!(!check.matches);
+    }
+
+    #[test]
+    fn values_match_lenient_decoding() {
+        for &input in &["4ZQ", "4zq-x", "-1-", "4Z!Q", "G000000000000", "--"] {
+            assert_eq!(
+                decode_lenient(input).ok(),
+                explain(input).value,
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn hyphens_are_flagged() {
+        let explanation = explain("4ZQ-X");
+
+        assert!(explanation.hyphenated);
+        assert!(decode("4ZQ-X").is_err());
+        assert!(!explain("4ZQX").hyphenated);
+    }
+
+    #[test]
+    fn oversized_values_still_explain() {
+        let explanation = explain("ZZZZZZZZZZZZZZ");
+
+        assert_eq!(None, explanation.value);
+        match explanation.symbols[0].reading {
+            Reading::Digit { shift, .. } => assert_eq!(65, shift),
+            _ => panic!("not a digit"),
+        }
+        assert!(explanation.check.is_some());
+    }
+
+    #[test]
+    fn empty_input_explains_nothing() {
+        let explanation = explain("--");
+
+        assert_eq!(None, explanation.value);
+        assert_eq!(None, explanation.check);
+    }
+}
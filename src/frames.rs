@@ -0,0 +1,203 @@
+//! A self-delimiting, text-safe wire format for streams of values.
+//!
+//! Each value is written as a frame: a single digit giving the length of the payload, followed by
+//! the payload itself, which is the value's ordinary encoding. Frames are written back to back
+//! with no separators.
+//!
+//! ```rust
+//! # use std::io;
+//! use crockford::frames::{FrameReader, FrameWriter};
+//!
+//! # fn run() -> io::Result<()> {
+//! let mut writer = FrameWriter::new(Vec::new());
+//! writer.write(5111)?;
+//! writer.write(0)?;
+//!
+//! let wire = writer.into_inner();
+//! assert_eq!(b"34ZQ10", &wire[..]);
+//!
+//! let values = FrameReader::new(&wire[..]).collect::<io::Result<Vec<_>>>()?;
+//! assert_eq!(vec![5111, 0], values);
+//! # Ok(())
+//! # }
+//! # run().unwrap()
+//! ```
+
+use std::io::{self, Read};
+
+use crate::{
+    decoding::to_normal_digit, encode_into, Write, MAX_ENCODED_LEN_U64, UPPERCASE_ENCODING,
+};
+
+/// Writes values as frames to an underlying writer.
+#[derive(Debug)]
+pub struct FrameWriter<W> {
+    inner: W,
+    frame: Frame,
+}
+
+impl<W: io::Write> FrameWriter<W> {
+    /// Creates a frame writer over the provided writer.
+    pub fn new(inner: W) -> FrameWriter<W> {
+        FrameWriter {
+            inner,
+            frame: Frame::default(),
+        }
+    }
+
+    /// Writes a single value as one frame.
+    pub fn write(&mut self, n: u64) -> io::Result<()> {
+        // Leave room for the header, then fill it in once the payload length is known.
+        self.frame.len = 1;
+        encode_into(n, &mut self.frame);
+        self.frame.buf[0] = UPPERCASE_ENCODING[self.frame.len - 1];
+
+        self.inner.write_all(&self.frame.buf[..self.frame.len])
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+#[derive(Debug)]
+struct Frame {
+    buf: [u8; MAX_ENCODED_LEN_U64 + 1],
+    len: usize,
+}
+
+impl Default for Frame {
+    fn default() -> Frame {
+        Frame {
+            buf: [0; MAX_ENCODED_LEN_U64 + 1],
+            len: 0,
+        }
+    }
+}
+
+impl Write for Frame {
+    fn write(&mut self, u: u8) {
+        self.buf[self.len] = u;
+        self.len += 1;
+    }
+}
+
+/// Reads frames from an underlying reader, yielding the values they contain.
+///
+/// Iteration ends cleanly when the reader is exhausted between frames. A stream that ends in
+/// the middle of a frame yields an `UnexpectedEof` error, and malformed frames yield
+/// `InvalidData` errors wrapping the decoding error.
+#[derive(Debug)]
+pub struct FrameReader<R> {
+    inner: R,
+}
+
+impl<R: Read> FrameReader<R> {
+    /// Creates a frame reader over the provided reader.
+    pub fn new(inner: R) -> FrameReader<R> {
+        FrameReader { inner }
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn read_frame(&mut self) -> io::Result<Option<u64>> {
+        let mut header = [0];
+        loop {
+            match self.inner.read(&mut header) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let len = usize::from(to_normal_digit(0, header[0]).map_err(invalid_data)?);
+        if len == 0 || len > MAX_ENCODED_LEN_U64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Frame length is out of range.",
+            ));
+        }
+
+        let mut payload = [0; MAX_ENCODED_LEN_U64];
+        self.inner.read_exact(&mut payload[..len])?;
+
+        let payload = std::str::from_utf8(&payload[..len]).map_err(invalid_data)?;
+        crate::decode(payload).map(Some).map_err(invalid_data)
+    }
+}
+
+impl<R: Read> Iterator for FrameReader<R> {
+    type Item = io::Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().transpose()
+    }
+}
+
+fn invalid_data<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::{FrameReader, FrameWriter};
+
+    #[test]
+    fn frames_round_trip() {
+        let values = [0, 1, 5111, 1 << 40, u64::MAX];
+
+        let mut writer = FrameWriter::new(Vec::new());
+        for &n in &values {
+            writer.write(n).unwrap();
+        }
+
+        let wire = writer.into_inner();
+        let actual: Vec<_> = FrameReader::new(&wire[..])
+            .collect::<io::Result<_>>()
+            .unwrap();
+
+        assert_eq!(&values[..], &actual[..]);
+    }
+
+    #[test]
+    fn empty_streams_have_no_frames() {
+        assert!(FrameReader::new(&b""[..]).next().is_none());
+    }
+
+    #[test]
+    fn truncated_frames_fail() {
+        let mut reader = FrameReader::new(&b"34Z"[..]);
+        let error = reader.next().unwrap().unwrap_err();
+
+        assert_eq!(io::ErrorKind::UnexpectedEof, error.kind());
+    }
+
+    #[test]
+    fn invalid_headers_fail() {
+        for &wire in &[&b"04ZQ"[..], b"Z4ZQ", b"!4ZQ"] {
+            let error = FrameReader::new(wire).next().unwrap().unwrap_err();
+            assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        }
+    }
+
+    #[test]
+    fn invalid_payloads_fail() {
+        let error = FrameReader::new(&b"34!Q"[..]).next().unwrap().unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+}
@@ -0,0 +1,127 @@
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use crate::{
+    encoding::{encode_padded, Digits},
+    MAX_ENCODED_LEN_U64,
+};
+
+/// Hashes values with any `Hasher` and displays the result as 13 Crockford digits.
+///
+/// Short hashes make convenient cache keys and deduplication IDs. Leading zeros are kept, so every
+/// hash has the same length. Keys are only as stable as the hasher: `DefaultHasher` may change
+/// between Rust releases, so use a hasher with a fixed algorithm for anything that is persisted.
+///
+/// ```rust
+/// use std::collections::hash_map::DefaultHasher;
+/// use crockford::HashDisplay;
+///
+/// let key = HashDisplay::new(DefaultHasher::new())
+///     .hash("users")
+///     .hash(&42u64)
+///     .to_string();
+///
+/// assert_eq!(13, key.len());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HashDisplay<H> {
+    hasher: H,
+}
+
+impl<H: Hasher> HashDisplay<H> {
+    /// Creates a display over the provided hasher, which may already have been fed some input.
+    pub fn new(hasher: H) -> HashDisplay<H> {
+        HashDisplay { hasher }
+    }
+
+    /// Feeds a value into the hash.
+    pub fn hash<T: Hash + ?Sized>(mut self, value: &T) -> HashDisplay<H> {
+        value.hash(&mut self.hasher);
+        self
+    }
+
+    /// Returns the underlying hasher.
+    pub fn into_inner(self) -> H {
+        self.hasher
+    }
+}
+
+/// Values can also be hashed into a `HashDisplay` directly.
+impl<H: Hasher> Hasher for HashDisplay<H> {
+    fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+    }
+}
+
+impl<H: Hasher> fmt::Display for HashDisplay<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut digits = Digits::<MAX_ENCODED_LEN_U64>::new();
+        encode_padded(
+            self.hasher.finish().into(),
+            MAX_ENCODED_LEN_U64,
+            &mut digits,
+        );
+        f.pad(digits.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::hash::{Hash, Hasher};
+
+    use crate::HashDisplay;
+
+    /// A hasher that just returns the last eight bytes written, for predictable output.
+    #[derive(Default)]
+    struct Last(u64);
+
+    impl Hasher for Last {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            for &u in bytes {
+                self.0 = self.0 << 8 | u64::from(u);
+            }
+        }
+    }
+
+    #[test]
+    fn hashes_are_padded_to_thirteen_digits() {
+        assert_eq!("0000000000000", HashDisplay::new(Last(0)).to_string());
+        assert_eq!(
+            "00000000004ZQ",
+            HashDisplay::new(Last::default())
+                .hash(&5111u64.to_be_bytes()[..])
+                .to_string()
+        );
+        assert_eq!(
+            "FZZZZZZZZZZZZ",
+            HashDisplay::new(Last(u64::MAX)).to_string()
+        );
+    }
+
+    #[test]
+    fn values_can_be_hashed_in_directly() {
+        let mut display = HashDisplay::new(Last::default());
+        5111u16.to_be_bytes().hash(&mut display);
+
+        assert_eq!(5111, display.finish());
+        assert_eq!(5111, display.into_inner().finish());
+    }
+
+    #[test]
+    fn width_flags_are_honored() {
+        assert_eq!(
+            "[0000000000001  ]",
+            format!("[{:<15}]", HashDisplay::new(Last(1)))
+        );
+    }
+}
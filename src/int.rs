@@ -0,0 +1,209 @@
+use core::convert::TryFrom;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{
+    decoding::decode_padded_bytes, encoding::encode_padded, error::Kind, Error, Result, Write,
+    MAX_ENCODED_LEN_U128, MAX_ENCODED_LEN_U32, MAX_ENCODED_LEN_U64,
+};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type that can be encoded and decoded with `encode_int` and `decode_int`.
+///
+/// This is implemented for `u8`, `u16`, `u32`, `u64`, `u128` and `usize`, and cannot be
+/// implemented outside this crate.
+pub trait CrockfordInt: Copy + private::Sealed {
+    /// The longest possible encoding of a value of this type, in digits.
+    const MAX_ENCODED_LEN: usize;
+
+    #[doc(hidden)]
+    fn to_u128(self) -> u128;
+
+    #[doc(hidden)]
+    fn from_u128(n: u128) -> Option<Self>;
+}
+
+macro_rules! crockford_int {
+    ($($t:ty => $len:expr),*) => {
+        $(
+            impl private::Sealed for $t {}
+
+            impl CrockfordInt for $t {
+                const MAX_ENCODED_LEN: usize = $len;
+
+                fn to_u128(self) -> u128 {
+                    self as u128
+                }
+
+                fn from_u128(n: u128) -> Option<Self> {
+                    <$t>::try_from(n).ok()
+                }
+            }
+        )*
+    };
+}
+
+crockford_int! {
+    u8 => 2,
+    u16 => 4,
+    u32 => MAX_ENCODED_LEN_U32,
+    u64 => MAX_ENCODED_LEN_U64,
+    u128 => MAX_ENCODED_LEN_U128,
+    usize => (usize::BITS as usize).div_ceil(5)
+}
+
+#[cfg(feature = "alloc")]
+/// Encodes any unsigned integer as a Crockford Base32-encoded string.
+///
+/// Values encode exactly as they would with `encode`, so a `u128` takes up to 26 digits.
+///
+/// ```rust
+/// assert_eq!("4ZQ", crockford::encode_int(5111u16));
+/// assert_eq!("7ZZZZZZZZZZZZZZZZZZZZZZZZZ", crockford::encode_int(u128::MAX));
+/// ```
+pub fn encode_int<N: CrockfordInt>(n: N) -> String {
+    let mut s = String::with_capacity(encoded_int_len(n));
+    encode_int_into(n, &mut s);
+    s
+}
+
+/// Returns the number of digits produced by encoding the provided unsigned integer.
+///
+/// This is `encoded_len` for any `CrockfordInt`, including `u128`.
+///
+/// ```rust
+/// assert_eq!(3, crockford::encoded_int_len(5111u16));
+/// assert_eq!(26, crockford::encoded_int_len(u128::MAX));
+/// ```
+pub fn encoded_int_len<N: CrockfordInt>(n: N) -> usize {
+    // Every digit carries five bits; zero still takes up one digit.
+    let bits = 128 - n.to_u128().leading_zeros() as usize;
+    bits.div_ceil(5).max(1)
+}
+
+/// Encodes any unsigned integer as Crockford Base32 and writes it to the provided output.
+pub fn encode_int_into<N: CrockfordInt, T: Write>(n: N, w: &mut T) {
+    encode_padded(n.to_u128(), encoded_int_len(n), w);
+}
+
+/// Attempts to decode a Crockford Base32-encoded string into any unsigned integer.
+///
+/// This accepts the same digits as `decode`. Values too large for the requested type fail with
+/// `OutOfRange`.
+///
+/// ```rust
+/// # use crockford::Error;
+/// # fn run() -> Result<(), Error> {
+/// assert_eq!(u128::MAX, crockford::decode_int("7zzzzzzzzzzzzzzzzzzzzzzzzz")?);
+/// assert_eq!(5111, crockford::decode_int::<u16>("4ZQ")?);
+/// assert!(crockford::decode_int::<u8>("4ZQ").is_err());
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+pub fn decode_int<N: CrockfordInt>(input: &str) -> Result<N> {
+    let input = input.as_bytes();
+    match input.len() {
+        0 => Err(Error::new(
+            Kind::EmptyString,
+            "Encoded input string is empty.",
+        )),
+
+        n if n > N::MAX_ENCODED_LEN => {
+            Err(Error::new(Kind::OutOfRange, "Encoded value is too large"))
+        }
+
+        n => decode_padded_bytes(input, n).and_then(|n| {
+            N::from_u128(n)
+                .ok_or_else(|| Error::new(Kind::OutOfRange, "Encoded value is too large"))
+        }),
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{
+        decode, decode_int, encode, encode_int, encoded_int_len, encoded_len, error::Kind,
+        CrockfordInt, Error,
+    };
+
+    #[test]
+    fn small_types_encode_like_u64() {
+        for &n in &[0, 1, 31, 32, 5111, u64::MAX] {
+            assert_eq!(encode(n), encode_int(n));
+            assert_eq!(encode(n), encode_int(n as usize));
+        }
+        assert_eq!(encode(255), encode_int(u8::MAX));
+        assert_eq!(encode(65535), encode_int(u16::MAX));
+        assert_eq!(encode(u32::MAX.into()), encode_int(u32::MAX));
+    }
+
+    #[test]
+    fn max_lengths_are_right() {
+        assert_eq!(u8::MAX_ENCODED_LEN, encode_int(u8::MAX).len());
+        assert_eq!(u16::MAX_ENCODED_LEN, encode_int(u16::MAX).len());
+        assert_eq!(u32::MAX_ENCODED_LEN, encode_int(u32::MAX).len());
+        assert_eq!(u64::MAX_ENCODED_LEN, encode_int(u64::MAX).len());
+        assert_eq!(u128::MAX_ENCODED_LEN, encode_int(u128::MAX).len());
+        assert_eq!(usize::MAX_ENCODED_LEN, encode_int(usize::MAX).len());
+    }
+
+    #[test]
+    fn encoded_int_len_matches_encode_int() {
+        for &n in &[0, 1, 31, 32, 5111, u64::MAX] {
+            assert_eq!(encoded_len(n), encoded_int_len(n));
+        }
+        for &n in &[u128::from(u64::MAX) + 1, 1 << 125, u128::MAX] {
+            assert_eq!(encode_int(n).len(), encoded_int_len(n));
+        }
+    }
+
+    #[test]
+    fn u128_round_trips() {
+        let values = [
+            0,
+            1,
+            u128::from(u64::MAX),
+            u128::from(u64::MAX) + 1,
+            0x0189_0a5d_ac96_774b_bcce_b302_099a_8057,
+            u128::MAX,
+        ];
+        for &n in &values {
+            assert_eq!(Ok(n), decode_int(&encode_int(n)));
+        }
+    }
+
+    #[test]
+    fn decoding_agrees_with_decode() {
+        for input in &["0", "4zq", "oIl", "FZZZZZZZZZZZZ"] {
+            assert_eq!(decode(input), decode_int::<u64>(input));
+        }
+    }
+
+    #[test]
+    fn values_too_large_for_the_type_fail() {
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+
+        assert_eq!(expected, decode_int::<u8>("80").map(u128::from));
+        assert_eq!(expected, decode_int::<u8>("000").map(u128::from));
+        assert_eq!(expected, decode_int::<u64>("G000000000000").map(u128::from));
+        assert_eq!(expected, decode_int::<u128>("80000000000000000000000000"));
+        assert_eq!(Ok(255), decode_int::<u8>("7Z"));
+    }
+
+    #[test]
+    fn bad_input_fails() {
+        assert_eq!(
+            Err(Error::new(Kind::EmptyString, "Don't care")),
+            decode_int::<u128>("")
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(1, b'!'), "Don't care")),
+            decode_int::<u128>("4!")
+        );
+    }
+}
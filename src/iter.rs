@@ -0,0 +1,97 @@
+use core::borrow::Borrow;
+
+use alloc::string::String;
+
+use crate::encode;
+
+/// Adds Crockford encoding to iterators over `u64` values.
+///
+/// ```rust
+/// use crockford::EncodeCrockford;
+///
+/// let ids = [5111, 1, u64::MAX];
+/// let encoded: Vec<_> = ids.iter().encode_crockford().collect();
+///
+/// assert_eq!(vec!["4ZQ", "1", "FZZZZZZZZZZZZ"], encoded);
+/// ```
+pub trait EncodeCrockford: Iterator + Sized
+where
+    Self::Item: Borrow<u64>,
+{
+    /// Encodes each value as it is yielded.
+    fn encode_crockford(self) -> EncodeIter<Self> {
+        EncodeIter { inner: self }
+    }
+}
+
+impl<I> EncodeCrockford for I
+where
+    I: Iterator,
+    I::Item: Borrow<u64>,
+{
+}
+
+/// An iterator that encodes the values of another.
+///
+/// This is created by [`EncodeCrockford::encode_crockford`].
+#[derive(Clone, Debug)]
+pub struct EncodeIter<I> {
+    inner: I,
+}
+
+impl<I> Iterator for EncodeIter<I>
+where
+    I: Iterator,
+    I::Item: Borrow<u64>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.inner.next().map(|n| encode(*n.borrow()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<I> DoubleEndedIterator for EncodeIter<I>
+where
+    I: DoubleEndedIterator,
+    I::Item: Borrow<u64>,
+{
+    fn next_back(&mut self) -> Option<String> {
+        self.inner.next_back().map(|n| encode(*n.borrow()))
+    }
+}
+
+impl<I> ExactSizeIterator for EncodeIter<I>
+where
+    I: ExactSizeIterator,
+    I::Item: Borrow<u64>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::EncodeCrockford;
+
+    #[test]
+    fn owned_and_borrowed_values_encode() {
+        let owned: Vec<_> = (31..34).encode_crockford().collect();
+        let borrowed: Vec<_> = [31, 32, 33].iter().encode_crockford().collect();
+
+        assert_eq!(vec!["Z", "10", "11"], owned);
+        assert_eq!(owned, borrowed);
+    }
+
+    #[test]
+    fn adapter_preserves_length_and_direction() {
+        let mut encoded = [1u64, 2, 3].iter().encode_crockford();
+
+        assert_eq!(3, encoded.len());
+        assert_eq!(Some("3".to_owned()), encoded.next_back());
+        assert_eq!(Some("1".to_owned()), encoded.next());
+        assert_eq!(1, encoded.len());
+    }
+}
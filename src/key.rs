@@ -0,0 +1,380 @@
+use core::convert::TryFrom;
+
+use alloc::string::String;
+
+use crate::{
+    encoding::encode_padded, error::Kind, Error, Result, MAX_ENCODED_LEN_U32, MAX_ENCODED_LEN_U64,
+};
+
+const U16_WIDTH: usize = 4;
+
+/// Flipping the sign bit maps `i64::MIN..=i64::MAX` onto `0..=u64::MAX` in order.
+const SIGN_BIT: u64 = 1 << 63;
+
+/// Builds a lexicographically sortable key out of several fixed-width fields.
+///
+/// Each field is zero-padded to the longest possible encoding of its type, so keys built from
+/// the same sequence of field types compare as strings in the same order as their fields compare
+/// as numbers. Use a `KeySplitter` with the same sequence of field types to take a key apart.
+///
+/// ```rust
+/// let key = crockford::KeyBuilder::new()
+///     .u64(1_571_000_000_000)
+///     .u32(7)
+///     .u16(42)
+///     .build();
+///
+/// assert_eq!("00001DQ3EB7G00000007001A", key);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct KeyBuilder {
+    buf: String,
+}
+
+impl KeyBuilder {
+    /// Creates an empty key builder.
+    pub fn new() -> KeyBuilder {
+        KeyBuilder::default()
+    }
+
+    /// Appends a `u64` field, thirteen digits wide.
+    pub fn u64(mut self, n: u64) -> KeyBuilder {
+        encode_padded(n.into(), MAX_ENCODED_LEN_U64, &mut self.buf);
+        self
+    }
+
+    /// Appends an `i64` field, thirteen digits wide.
+    ///
+    /// Negative values sort before positive ones.
+    pub fn i64(self, n: i64) -> KeyBuilder {
+        self.u64(n as u64 ^ SIGN_BIT)
+    }
+
+    /// Appends a `u32` field, seven digits wide.
+    pub fn u32(mut self, n: u32) -> KeyBuilder {
+        encode_padded(n.into(), MAX_ENCODED_LEN_U32, &mut self.buf);
+        self
+    }
+
+    /// Appends a `u16` field, four digits wide.
+    pub fn u16(mut self, n: u16) -> KeyBuilder {
+        encode_padded(n.into(), U16_WIDTH, &mut self.buf);
+        self
+    }
+
+    /// Returns the finished key.
+    pub fn build(self) -> String {
+        self.buf
+    }
+}
+
+/// Takes apart a key created by `KeyBuilder`.
+///
+/// Fields must be read back in the same order and with the same types used to build the key.
+///
+/// ```rust
+/// # use crockford::{Error, KeySplitter};
+/// # fn run() -> Result<(), Error> {
+/// let mut splitter = KeySplitter::new("00001DQ3EB7G00000007001A");
+///
+/// assert_eq!(1_571_000_000_000, splitter.u64()?);
+/// assert_eq!(7, splitter.u32()?);
+/// assert_eq!(42, splitter.u16()?);
+/// splitter.finish()?;
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeySplitter<'a> {
+    rest: &'a str,
+}
+
+impl<'a> KeySplitter<'a> {
+    /// Creates a splitter over the provided key.
+    pub fn new(key: &'a str) -> KeySplitter<'a> {
+        KeySplitter { rest: key }
+    }
+
+    /// Reads the next thirteen digits as a `u64` field.
+    pub fn u64(&mut self) -> Result<u64> {
+        self.field(MAX_ENCODED_LEN_U64)
+    }
+
+    /// Reads the next thirteen digits as an `i64` field.
+    pub fn i64(&mut self) -> Result<i64> {
+        self.u64().map(|n| (n ^ SIGN_BIT) as i64)
+    }
+
+    /// Reads the next seven digits as a `u32` field.
+    pub fn u32(&mut self) -> Result<u32> {
+        let n = self.field(MAX_ENCODED_LEN_U32)?;
+        u32::try_from(n).map_err(|_| Error::new(Kind::OutOfRange, "Key field is too large"))
+    }
+
+    /// Reads the next four digits as a `u16` field.
+    pub fn u16(&mut self) -> Result<u16> {
+        let n = self.field(U16_WIDTH)?;
+        u16::try_from(n).map_err(|_| Error::new(Kind::OutOfRange, "Key field is too large"))
+    }
+
+    /// Verifies that every field in the key has been read.
+    pub fn finish(self) -> Result<()> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::new(Kind::InvalidLength, "Key has unread digits"))
+        }
+    }
+
+    fn field(&mut self, width: usize) -> Result<u64> {
+        let (field, rest) = match (self.rest.get(..width), self.rest.get(width..)) {
+            (Some(field), Some(rest)) => (field, rest),
+            _ => return Err(Error::new(Kind::InvalidLength, "Key is too short")),
+        };
+
+        let n = crate::decode(field)?;
+        self.rest = rest;
+        Ok(n)
+    }
+}
+
+/// Encodes a `u64` value as a key that sorts as a string in the same order as the value.
+///
+/// This is a key with a single `u64` field: the value zero-padded to thirteen digits.
+///
+/// ```rust
+/// assert_eq!("00000000004ZQ", crockford::encode_sorted_u64(5111));
+/// assert!(crockford::encode_sorted_u64(32) > crockford::encode_sorted_u64(31));
+/// ```
+pub fn encode_sorted_u64(n: u64) -> String {
+    KeyBuilder::new().u64(n).build()
+}
+
+/// Encodes an `i64` value as a key that sorts as a string in the same order as the value.
+///
+/// The value is offset so that `i64::MIN` encodes as all zeros and negative numbers sort before
+/// positive ones, then zero-padded to thirteen digits. This is not the ordinary encoding of the
+/// value, so decode it with `decode_sorted_i64`.
+///
+/// ```rust
+/// assert_eq!("7ZZZZZZZZZZZZ", crockford::encode_sorted_i64(-1));
+/// assert_eq!("8000000000000", crockford::encode_sorted_i64(0));
+/// assert!(crockford::encode_sorted_i64(-5111) < crockford::encode_sorted_i64(1));
+/// ```
+pub fn encode_sorted_i64(n: i64) -> String {
+    KeyBuilder::new().i64(n).build()
+}
+
+/// Attempts to decode a key made by `encode_sorted_u64`.
+///
+/// The key must be exactly thirteen digits long.
+pub fn decode_sorted_u64<T: AsRef<str>>(input: T) -> Result<u64> {
+    let mut splitter = KeySplitter::new(input.as_ref());
+    let n = splitter.u64()?;
+    splitter.finish()?;
+    Ok(n)
+}
+
+/// Attempts to decode a key made by `encode_sorted_i64`.
+///
+/// The key must be exactly thirteen digits long.
+pub fn decode_sorted_i64<T: AsRef<str>>(input: T) -> Result<i64> {
+    let mut splitter = KeySplitter::new(input.as_ref());
+    let n = splitter.i64()?;
+    splitter.finish()?;
+    Ok(n)
+}
+
+/// Returns the range of keys whose leading `u64` field begins with the provided high bits.
+///
+/// The first `bits` bits of the field must equal `prefix`. The range is half-open: `start` is the
+/// smallest matching key and `end`, if any, is the first key past the matching ones. There is no
+/// end when the prefix extends to the top of the key space. Keys with more fields after the
+/// leading `u64` fall into the same range, which makes this suitable for range scans over keys
+/// built with `KeyBuilder`.
+///
+/// ```rust
+/// let (start, end) = crockford::range_for_prefix(0b1, 1);
+///
+/// assert_eq!("8000000000000", start);
+/// assert_eq!(None, end);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `bits` is greater than 64 or if `prefix` does not fit in `bits` bits.
+pub fn range_for_prefix(prefix: u64, bits: u32) -> (String, Option<String>) {
+    assert!(bits <= 64, "prefix cannot be longer than 64 bits");
+    assert!(
+        bits == 64 || prefix >> bits == 0,
+        "prefix does not fit in the requested number of bits"
+    );
+
+    let free = 64 - bits;
+    let start = prefix.checked_shl(free).unwrap_or(0);
+    let last = start | u64::MAX.checked_shr(bits).unwrap_or(0);
+
+    let start = KeyBuilder::new().u64(start).build();
+    let end = last
+        .checked_add(1)
+        .map(|end| KeyBuilder::new().u64(end).build());
+
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        decode_sorted_i64, decode_sorted_u64, encode_sorted_i64, encode_sorted_u64, error::Kind,
+        range_for_prefix, Error, KeyBuilder, KeySplitter,
+    };
+
+    /// A deterministic spread of values over the whole range, for order checks.
+    fn spread() -> Vec<u64> {
+        let mut values: Vec<u64> = (0..2000u64)
+            .map(|n| n.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+            .collect();
+        values.extend(&[0, 1, 31, 32, u64::MAX - 1, u64::MAX, 1 << 63, (1 << 63) - 1]);
+        values
+    }
+
+    #[test]
+    fn fields_round_trip() {
+        let key = KeyBuilder::new().u64(u64::MAX).u32(0).u16(u16::MAX).build();
+        let mut splitter = KeySplitter::new(&key);
+
+        assert_eq!(24, key.len());
+        assert_eq!(Ok(u64::MAX), splitter.u64());
+        assert_eq!(Ok(0), splitter.u32());
+        assert_eq!(Ok(u16::MAX), splitter.u16());
+        assert_eq!(Ok(()), splitter.finish());
+    }
+
+    #[test]
+    fn keys_sort_like_fields() {
+        let fields = [(1, 9, 9), (1, 10, 0), (2, 0, 0), (40, 0, 0), (40, 0, 1)];
+        let keys: Vec<_> = fields
+            .iter()
+            .map(|&(a, b, c)| KeyBuilder::new().u64(a).u32(b).u16(c).build())
+            .collect();
+
+        let mut sorted = keys.clone();
+        sorted.sort();
+
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn sorted_encodings_preserve_order() {
+        let unsigned = spread();
+        let signed: Vec<_> = unsigned.iter().map(|&n| n as i64).collect();
+
+        for pair in unsigned.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(a.cmp(&b), encode_sorted_u64(a).cmp(&encode_sorted_u64(b)));
+        }
+        for pair in signed.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            assert_eq!(a.cmp(&b), encode_sorted_i64(a).cmp(&encode_sorted_i64(b)));
+        }
+    }
+
+    #[test]
+    fn sorted_encodings_round_trip() {
+        for n in spread() {
+            assert_eq!(Ok(n), decode_sorted_u64(encode_sorted_u64(n)));
+            assert_eq!(Ok(n as i64), decode_sorted_i64(encode_sorted_i64(n as i64)));
+        }
+    }
+
+    #[test]
+    fn signed_bounds_encode_at_the_ends() {
+        assert_eq!("0000000000000", encode_sorted_i64(i64::MIN));
+        assert_eq!("FZZZZZZZZZZZZ", encode_sorted_i64(i64::MAX));
+
+        let key = KeyBuilder::new().i64(-1).u16(7).build();
+        let mut splitter = KeySplitter::new(&key);
+        assert_eq!(Ok(-1), splitter.i64());
+        assert_eq!(Ok(7), splitter.u16());
+    }
+
+    #[test]
+    fn sorted_decoding_requires_the_full_width() {
+        assert_eq!(
+            Err(Error::new(Kind::InvalidLength, "Don't care")),
+            decode_sorted_u64("4ZQ")
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidLength, "Don't care")),
+            decode_sorted_i64("80000000000000")
+        );
+        assert_eq!(
+            Err(Error::new(Kind::OutOfRange, "Don't care")),
+            decode_sorted_i64("G000000000000")
+        );
+    }
+
+    #[test]
+    fn short_keys_fail() {
+        let mut splitter = KeySplitter::new("000000000000");
+        let expected = Err(Error::new(Kind::InvalidLength, "Don't care"));
+
+        assert_eq!(expected, splitter.u64());
+    }
+
+    #[test]
+    fn leftover_digits_fail() {
+        let mut splitter = KeySplitter::new("00000");
+        let expected = Err(Error::new(Kind::InvalidLength, "Don't care"));
+
+        assert_eq!(Ok(0), splitter.u16());
+        assert_eq!(expected, splitter.finish());
+    }
+
+    #[test]
+    fn oversized_fields_fail() {
+        let mut splitter = KeySplitter::new("ZZZZ");
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+
+        assert_eq!(expected, splitter.u16());
+    }
+
+    #[test]
+    fn prefix_range_bounds_matching_keys() {
+        let (start, end) = range_for_prefix(0xABC, 12);
+        let end = end.unwrap();
+
+        let inside = [0xABC0_0000_0000_0000, 0xABCF_FFFF_FFFF_FFFF];
+        let outside = [0xABBF_FFFF_FFFF_FFFF, 0xABD0_0000_0000_0000];
+
+        for &n in &inside {
+            let key = KeyBuilder::new().u64(n).u16(7).build();
+            assert!(start <= key && key < end);
+        }
+
+        for &n in &outside {
+            let key = KeyBuilder::new().u64(n).u16(7).build();
+            assert!(key < start || end <= key);
+        }
+    }
+
+    #[test]
+    fn empty_prefix_covers_everything() {
+        assert_eq!(("0000000000000".to_owned(), None), range_for_prefix(0, 0));
+    }
+
+    #[test]
+    fn full_prefix_covers_one_value() {
+        let (start, end) = range_for_prefix(5111, 64);
+
+        assert_eq!("00000000004ZQ", start);
+        assert_eq!(Some("00000000004ZR".to_owned()), end);
+    }
+
+    #[test]
+    #[should_panic]
+    fn oversized_prefix_panics() {
+        range_for_prefix(0b100, 2);
+    }
+}
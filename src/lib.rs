@@ -27,8 +27,7 @@
 //! # use crockford;
 //! # use std::str;
 //! # fn run() -> Result<(), str::Utf8Error> {
-//! // The longest possible representation of u64 is 13 digits.
-//! let mut buf = Vec::with_capacity(13);
+//! let mut buf = Vec::with_capacity(crockford::MAX_ENCODED_LEN_U64);
 //! crockford::encode_into(5111, &mut buf);
 //!
 //! let result = std::str::from_utf8(&buf)?;
@@ -39,6 +38,16 @@
 //!
 //! This `encode_into` method also accepts `&mut String`, if you prefer.
 //!
+//! ### Other integer types
+//!
+//! `encode_int` and `decode_int` work with any unsigned integer type, including `u128`.
+//!
+//! ```rust
+//! let id = crockford::encode_int(u128::MAX);
+//!
+//! assert_eq!(26, id.len());
+//! ```
+//!
 //! ## Decoding
 //!
 //! Use the decode function to decode Crockford Base32-encoded strings. This operation can fail;
@@ -56,15 +65,146 @@
 //! # }
 //! # run().unwrap()
 //! ```
+//!
+//! ## `no_std`
+//!
+//! The default `std` feature can be turned off to use the crate without the standard library.
+//! `encode_into`, `encode_to_array`, `display`, `decode` and the ID types still work with no
+//! allocator at all; the `alloc` feature brings back everything that returns a `String` or
+//! `Vec`. I/O, clocks and random generation need `std`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(feature = "alloc")]
+mod abbrev;
+#[cfg(feature = "std")]
+mod bulk;
+mod bytes;
+mod check;
+#[cfg(feature = "rand")]
+pub mod codes;
+#[cfg(feature = "cuid2")]
+mod cuid2;
+#[cfg(feature = "cursor")]
+pub mod cursor;
 mod decoding;
+#[cfg(feature = "alloc")]
+mod diagnostic;
+#[cfg(feature = "alloc")]
+mod distance;
+#[cfg(feature = "rand")]
+mod distribution;
 mod encoding;
 mod error;
+#[cfg(feature = "alloc")]
+mod explain;
+#[cfg(feature = "std")]
+pub mod frames;
+mod hash;
+mod int;
+#[cfg(feature = "alloc")]
+mod iter;
+#[cfg(feature = "alloc")]
+mod key;
+#[cfg(feature = "std")]
+mod net;
+#[cfg(feature = "signed")]
+pub mod otp;
+#[cfg(feature = "alloc")]
+mod parity;
+#[cfg(kani)]
+mod proofs;
+mod scru128;
+#[cfg(feature = "serde")]
+pub mod serde_u64;
+#[cfg(feature = "signed")]
+pub mod signed;
+#[cfg(feature = "std")]
+mod time;
+#[cfg(feature = "alloc")]
+mod token;
+#[cfg(feature = "alloc")]
+mod typeid;
+mod ulid;
+mod uuid7;
+#[cfg(feature = "alloc")]
+mod varint;
 
-pub use decoding::decode;
+#[cfg(feature = "alloc")]
+pub use abbrev::{abbreviate, decode_prefix};
+#[cfg(feature = "mmap")]
+pub use bulk::decode_file;
+#[cfg(feature = "std")]
+pub use bulk::{decode_all, decode_lines, decode_stream, encode_stream, write_lines};
+#[cfg(feature = "alloc")]
+pub use bytes::{decode_bytes, decode_bytes_into, encode_bytes};
+pub use bytes::{encode_bytes_into, encoded_bytes_len};
+pub use check::decode_with_check;
+#[cfg(feature = "alloc")]
+pub use check::encode_with_check;
+#[cfg(feature = "cuid2")]
+pub use cuid2::Cuid2Generator;
+#[cfg(feature = "alloc")]
+pub use decoding::decode_list;
+pub use decoding::{
+    decode, decode_const, decode_grouped, decode_lenient, decode_records, decode_signed_display,
+    decode_strict,
+};
+#[cfg(feature = "alloc")]
+pub use diagnostic::{
+    decode_diagnostic, decode_diagnostic_with_check, ChecksumStatus, Correction, Decoded,
+};
+#[cfg(feature = "alloc")]
+pub use distance::{edit_distance, generate_distant};
+#[cfg(feature = "rand")]
+pub use distribution::CrockfordAlphanumeric;
 pub use encoding::*;
-pub use error::Error;
+#[cfg(feature = "std")]
+pub use error::LineError;
+pub use error::{Error, Kind};
+#[cfg(feature = "alloc")]
+pub use explain::{explain, Check, Explanation, Reading, Symbol};
+pub use hash::HashDisplay;
+#[cfg(feature = "alloc")]
+pub use int::encode_int;
+pub use int::{decode_int, encode_int_into, encoded_int_len, CrockfordInt};
+#[cfg(feature = "alloc")]
+pub use iter::{EncodeCrockford, EncodeIter};
+#[cfg(feature = "alloc")]
+pub use key::{
+    decode_sorted_i64, decode_sorted_u64, encode_sorted_i64, encode_sorted_u64, range_for_prefix,
+    KeyBuilder, KeySplitter,
+};
+#[cfg(feature = "std")]
+pub use net::{
+    decode_eui64, decode_ipv4, decode_ipv6, decode_mac, encode_eui64, encode_ipv4, encode_ipv6,
+    encode_mac,
+};
+#[cfg(feature = "alloc")]
+pub use parity::{decode_with_parity, encode_with_parity, Repaired, MAX_PARITY};
+pub use scru128::Scru128Id;
+#[cfg(feature = "rand")]
+pub use scru128::{Scru128Generator, Scru128State};
+#[cfg(feature = "std")]
+pub use time::{
+    decode_duration, decode_system_time, encode_duration, encode_system_time, Resolution,
+};
+#[cfg(feature = "alloc")]
+pub use token::TokenProfile;
+#[cfg(feature = "alloc")]
+pub use typeid::TypeId;
+pub use ulid::Ulid;
+#[cfg(feature = "rand")]
+pub use ulid::UlidGenerator;
+pub use uuid7::Uuid7;
+#[cfg(feature = "rand")]
+pub use uuid7::Uuid7Generator;
+#[cfg(feature = "alloc")]
+pub use varint::{from_leb128, to_leb128};
 
-pub type Result<T, E = Error> = std::result::Result<T, E>;
+pub type Result<T, E = Error> = core::result::Result<T, E>;
 
-static UPPERCASE_ENCODING: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const UPPERCASE_ENCODING: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
@@ -0,0 +1,159 @@
+use std::{
+    convert::TryFrom,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use crate::{
+    decoding::decode_padded, encoding::encode_padded, error::Kind, Error, Result,
+    MAX_ENCODED_LEN_U128, MAX_ENCODED_LEN_U32, MAX_ENCODED_LEN_U64,
+};
+
+// Forty-eight bits take up ten digits.
+const MAC_WIDTH: usize = 10;
+
+/// Encodes an IPv4 address as exactly seven digits.
+///
+/// ```rust
+/// # use std::net::Ipv4Addr;
+/// let encoded = crockford::encode_ipv4(Ipv4Addr::new(192, 168, 0, 1));
+///
+/// assert_eq!("30AG001", encoded);
+/// ```
+pub fn encode_ipv4(addr: Ipv4Addr) -> String {
+    let mut s = String::with_capacity(MAX_ENCODED_LEN_U32);
+    encode_padded(u32::from(addr).into(), MAX_ENCODED_LEN_U32, &mut s);
+    s
+}
+
+/// Attempts to decode a seven-digit encoded IPv4 address.
+pub fn decode_ipv4<T: AsRef<str>>(input: T) -> Result<Ipv4Addr> {
+    let n = decode_padded(input.as_ref(), MAX_ENCODED_LEN_U32)?;
+    u32::try_from(n)
+        .map(Ipv4Addr::from)
+        .map_err(|_| Error::new(Kind::OutOfRange, "Encoded value is too large"))
+}
+
+/// Encodes an IPv6 address as exactly twenty-six digits.
+pub fn encode_ipv6(addr: Ipv6Addr) -> String {
+    let mut s = String::with_capacity(MAX_ENCODED_LEN_U128);
+    encode_padded(u128::from(addr), MAX_ENCODED_LEN_U128, &mut s);
+    s
+}
+
+/// Attempts to decode a twenty-six-digit encoded IPv6 address.
+pub fn decode_ipv6<T: AsRef<str>>(input: T) -> Result<Ipv6Addr> {
+    decode_padded(input.as_ref(), MAX_ENCODED_LEN_U128).map(Ipv6Addr::from)
+}
+
+/// Encodes a 48-bit MAC address as exactly ten digits.
+///
+/// ```rust
+/// let encoded = crockford::encode_mac([0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+///
+/// assert_eq!("0038NKRKAY", encoded);
+/// ```
+pub fn encode_mac(mac: [u8; 6]) -> String {
+    let n = mac.iter().fold(0u128, |n, &u| n << 8 | u128::from(u));
+    let mut s = String::with_capacity(MAC_WIDTH);
+    encode_padded(n, MAC_WIDTH, &mut s);
+    s
+}
+
+/// Attempts to decode a ten-digit encoded MAC address.
+pub fn decode_mac<T: AsRef<str>>(input: T) -> Result<[u8; 6]> {
+    let n = decode_padded(input.as_ref(), MAC_WIDTH)?;
+    if n >> 48 != 0 {
+        return Err(Error::new(Kind::OutOfRange, "Encoded value is too large"));
+    }
+
+    let mut mac = [0; 6];
+    mac.copy_from_slice(&n.to_be_bytes()[10..]);
+    Ok(mac)
+}
+
+/// Encodes a 64-bit EUI-64 identifier as exactly thirteen digits.
+pub fn encode_eui64(eui: u64) -> String {
+    let mut s = String::with_capacity(MAX_ENCODED_LEN_U64);
+    encode_padded(eui.into(), MAX_ENCODED_LEN_U64, &mut s);
+    s
+}
+
+/// Attempts to decode a thirteen-digit encoded EUI-64 identifier.
+pub fn decode_eui64<T: AsRef<str>>(input: T) -> Result<u64> {
+    let n = decode_padded(input.as_ref(), MAX_ENCODED_LEN_U64)?;
+    u64::try_from(n).map_err(|_| Error::new(Kind::OutOfRange, "Encoded value is too large"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use crate::{
+        decode_eui64, decode_ipv4, decode_ipv6, decode_mac, encode_eui64, encode_ipv4, encode_ipv6,
+        encode_mac, error::Kind, Error,
+    };
+
+    #[test]
+    fn ipv4_round_trips() {
+        for &addr in &[
+            Ipv4Addr::UNSPECIFIED,
+            Ipv4Addr::LOCALHOST,
+            Ipv4Addr::BROADCAST,
+        ] {
+            let encoded = encode_ipv4(addr);
+
+            assert_eq!(7, encoded.len());
+            assert_eq!(Ok(addr), decode_ipv4(encoded.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn ipv6_round_trips() {
+        let max = Ipv6Addr::from(u128::MAX);
+
+        for &addr in &[Ipv6Addr::UNSPECIFIED, Ipv6Addr::LOCALHOST, max] {
+            let encoded = encode_ipv6(addr);
+
+            assert_eq!(26, encoded.len());
+            assert_eq!(Ok(addr), decode_ipv6(encoded.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn oversized_ipv4_fails() {
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+        assert_eq!(expected, decode_ipv4("4000000"));
+    }
+
+    #[test]
+    fn short_ipv6_fails() {
+        let expected = Err(Error::new(Kind::InvalidLength, "Don't care"));
+        assert_eq!(expected, decode_ipv6("1"));
+    }
+
+    #[test]
+    fn mac_round_trips() {
+        for &mac in &[[0; 6], [0xFF; 6], [0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]] {
+            let encoded = encode_mac(mac);
+
+            assert_eq!(10, encoded.len());
+            assert_eq!(Ok(mac), decode_mac(encoded.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn oversized_mac_fails() {
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+        assert_eq!(expected, decode_mac("G000000000"));
+    }
+
+    #[test]
+    fn eui64_round_trips() {
+        for &eui in &[0, 0x0200_5EFF_FE00_5301, u64::MAX] {
+            let encoded = encode_eui64(eui);
+
+            assert_eq!(13, encoded.len());
+            assert_eq!(Ok(eui), decode_eui64(encoded));
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Short codes derived from a shared secret and the current time window.
+//!
+//! This is the TOTP recipe rendered in Crockford digits: the number of whole time steps since the
+//! Unix epoch is signed with HMAC-SHA256, and the leading bits of the result are encoded as the
+//! code. Both sides of a device-pairing or verification flow compute the same code from the same
+//! secret, so nothing has to be stored or sent ahead of time.
+//!
+//! ```rust
+//! # use std::time::{Duration, UNIX_EPOCH};
+//! use crockford::otp::CodeGenerator;
+//!
+//! let generator = CodeGenerator::new(b"shared secret").digits(8);
+//! let now = UNIX_EPOCH + Duration::from_secs(1_571_000_000);
+//! let code = generator.code_at(now);
+//!
+//! assert_eq!(8, code.len());
+//! assert!(generator.verify_at(&code.to_lowercase(), now + Duration::from_secs(20)));
+//! ```
+
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use hmac::Mac;
+
+use crate::{
+    decoding::decode_padded,
+    encoding::encode_padded,
+    signed::{mac_for, HmacSha256},
+};
+
+/// The largest number of windows on either side of the current one that a generator may accept.
+pub const MAX_SKEW: u64 = 10;
+
+/// Generates and validates time-window codes.
+#[derive(Clone)]
+pub struct CodeGenerator {
+    mac: HmacSha256,
+    step: Duration,
+    digits: usize,
+    skew: u64,
+}
+
+impl CodeGenerator {
+    /// Creates a generator for six-digit codes that change every thirty seconds.
+    ///
+    /// By default, codes from one window on either side of the current one are also accepted to
+    /// allow for clock drift and slow typists.
+    pub fn new(secret: &[u8]) -> CodeGenerator {
+        CodeGenerator {
+            mac: mac_for(secret),
+            step: Duration::from_secs(30),
+            digits: 6,
+            skew: 1,
+        }
+    }
+
+    /// Sets how long each code remains current.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is shorter than one second.
+    pub fn step(mut self, step: Duration) -> CodeGenerator {
+        assert!(step.as_secs() > 0, "step must be at least one second");
+        self.step = step;
+        self
+    }
+
+    /// Sets the number of digits in each code.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless the number of digits is between 1 and 12.
+    pub fn digits(mut self, digits: usize) -> CodeGenerator {
+        assert!((1..=12).contains(&digits), "codes must have 1 to 12 digits");
+        self.digits = digits;
+        self
+    }
+
+    /// Sets how many windows before and after the current one are also accepted.
+    ///
+    /// Every accepted window makes a guessed code more likely to pass, so keep this small.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the skew is greater than `MAX_SKEW`.
+    pub fn skew(mut self, windows: u64) -> CodeGenerator {
+        assert!(
+            windows <= MAX_SKEW,
+            "skew must be at most {} windows",
+            MAX_SKEW
+        );
+        self.skew = windows;
+        self
+    }
+
+    /// Returns the code for the current time.
+    pub fn code(&self) -> String {
+        self.code_at(SystemTime::now())
+    }
+
+    /// Returns the code for the provided time.
+    pub fn code_at(&self, time: SystemTime) -> String {
+        let mut code = String::with_capacity(self.digits);
+        encode_padded(self.value(self.window(time)).into(), self.digits, &mut code);
+        code
+    }
+
+    /// Checks a code against the current time.
+    pub fn verify(&self, code: &str) -> bool {
+        self.verify_at(code, SystemTime::now())
+    }
+
+    /// Checks a code against the provided time, allowing for the configured skew.
+    ///
+    /// Codes are read case-insensitively, with the usual folding of ambiguous symbols.
+    pub fn verify_at(&self, code: &str, time: SystemTime) -> bool {
+        let actual = match decode_padded(code, self.digits) {
+            Ok(n) => n as u64,
+            Err(_) => return false,
+        };
+
+        let window = self.window(time);
+        let first = window.saturating_sub(self.skew);
+        let last = window.saturating_add(self.skew);
+
+        // Check every window so that timing does not reveal which one matched.
+        (first..=last).fold(false, |found, window| {
+            found | (self.value(window) ^ actual == 0)
+        })
+    }
+
+    fn window(&self, time: SystemTime) -> u64 {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        since_epoch.as_secs() / self.step.as_secs()
+    }
+
+    fn value(&self, window: u64) -> u64 {
+        let digest = self
+            .mac
+            .clone()
+            .chain_update(window.to_be_bytes())
+            .finalize();
+
+        let mut leading = [0; 8];
+        leading.copy_from_slice(&digest.into_bytes()[..8]);
+        u64::from_be_bytes(leading) >> (64 - 5 * self.digits)
+    }
+}
+
+impl fmt::Debug for CodeGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Never print key material.
+        f.debug_struct("CodeGenerator")
+            .field("step", &self.step)
+            .field("digits", &self.digits)
+            .field("skew", &self.skew)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{CodeGenerator, MAX_SKEW};
+
+    #[test]
+    fn codes_are_stable_within_a_window() {
+        let generator = CodeGenerator::new(b"secret");
+        let start = UNIX_EPOCH + Duration::from_secs(300);
+
+        assert_eq!(
+            generator.code_at(start),
+            generator.code_at(start + Duration::from_secs(29))
+        );
+    }
+
+    #[test]
+    fn codes_change_between_windows() {
+        let generator = CodeGenerator::new(b"secret").digits(12);
+        let start = UNIX_EPOCH + Duration::from_secs(300);
+
+        assert_ne!(
+            generator.code_at(start),
+            generator.code_at(start + Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn skew_is_honored() {
+        let generator = CodeGenerator::new(b"secret").digits(12).skew(1);
+        let start = UNIX_EPOCH + Duration::from_secs(300);
+        let code = generator.code_at(start);
+
+        assert!(generator.verify_at(&code, start + Duration::from_secs(30)));
+        assert!(!generator.verify_at(&code, start + Duration::from_secs(60)));
+        assert!(!generator
+            .skew(0)
+            .verify_at(&code, start + Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn skew_is_honored_at_the_epoch() {
+        let generator = CodeGenerator::new(b"secret").digits(12).skew(MAX_SKEW);
+        let code = generator.code_at(UNIX_EPOCH);
+
+        assert!(generator.verify_at(&code, UNIX_EPOCH));
+        assert!(generator.verify_at(&code, UNIX_EPOCH + Duration::from_secs(30 * MAX_SKEW)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn excessive_skew_panics() {
+        let _ = CodeGenerator::new(b"secret").skew(MAX_SKEW + 1);
+    }
+
+    #[test]
+    fn other_secrets_fail() {
+        let start = UNIX_EPOCH + Duration::from_secs(300);
+        let code = CodeGenerator::new(b"secret").digits(12).code_at(start);
+
+        assert!(!CodeGenerator::new(b"other")
+            .digits(12)
+            .verify_at(&code, start));
+    }
+
+    #[test]
+    fn malformed_codes_fail() {
+        let generator = CodeGenerator::new(b"secret");
+        let start = UNIX_EPOCH;
+
+        assert!(!generator.verify_at("", start));
+        assert!(!generator.verify_at("1234567", start));
+        assert!(!generator.verify_at("12345!", start));
+    }
+}
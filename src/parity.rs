@@ -0,0 +1,411 @@
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{
+    check::digit_value, decoding::to_normal_digit, encode_into, error::Kind, Error, Result,
+    MAX_ENCODED_LEN_U64, UPPERCASE_ENCODING,
+};
+
+/// The largest number of parity symbols that fits alongside any `u64` in a 31-symbol codeword.
+pub const MAX_PARITY: usize = 31 - MAX_ENCODED_LEN_U64;
+
+// Log and antilog tables for GF(32), generated by x^5 + x^2 + 1. The antilog table is doubled so
+// that products can be looked up without reducing the sum of logs.
+const TABLES: ([u8; 62], [u8; 32]) = tables();
+const EXP: [u8; 62] = TABLES.0;
+const LOG: [u8; 32] = TABLES.1;
+
+const fn tables() -> ([u8; 62], [u8; 32]) {
+    let mut exp = [0; 62];
+    let mut log = [0; 32];
+    let mut x = 1u8;
+    let mut i = 0;
+
+    while i < 31 {
+        exp[i] = x;
+        exp[i + 31] = x;
+        log[x as usize] = i as u8;
+
+        x <<= 1;
+        if x & 0x20 != 0 {
+            x ^= 0x25;
+        }
+        i += 1;
+    }
+
+    (exp, log)
+}
+
+/// A value recovered from a codeword, with the positions of any symbols that had to be repaired.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repaired {
+    /// The decoded value.
+    pub value: u64,
+
+    /// The index of every symbol in the input that was corrected, in ascending order.
+    pub repaired: Vec<usize>,
+}
+
+/// Encodes a `u64` value followed by Reed-Solomon parity symbols.
+///
+/// Each pair of parity symbols lets `decode_with_parity` correct one corrupted symbol anywhere in
+/// the output, so two parity symbols repair a single typo and four repair two. This is meant for
+/// codes printed on physical media, where a smudge should not make a code unreadable.
+///
+/// ```rust
+/// # use crockford::Error;
+/// # fn run() -> Result<(), Error> {
+/// let code = crockford::encode_with_parity(5111, 4);
+/// assert_eq!(7, code.len());
+///
+/// // Smudge two symbols.
+/// let smudged = format!("X{}Y{}", &code[1..3], &code[4..]);
+/// let repaired = crockford::decode_with_parity(&smudged, 4)?;
+///
+/// assert_eq!(5111, repaired.value);
+/// assert_eq!(vec![0, 3], repaired.repaired);
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+///
+/// # Panics
+///
+/// Panics if `parity` is greater than `MAX_PARITY`.
+pub fn encode_with_parity(n: u64, parity: usize) -> String {
+    assert!(parity <= MAX_PARITY, "too many parity symbols");
+
+    let mut s = String::with_capacity(MAX_ENCODED_LEN_U64 + parity);
+    encode_into(n, &mut s);
+
+    let data: Vec<u8> = s.bytes().map(digit_value).collect();
+    for digit in parity_symbols(&data, parity) {
+        s.push(char::from(UPPERCASE_ENCODING[usize::from(digit)]));
+    }
+    s
+}
+
+/// Attempts to decode a value written by `encode_with_parity`, repairing corrupted symbols.
+///
+/// Up to `parity / 2` symbols can be repaired. Fails if more symbols than that were corrupted and
+/// the damage is detected, though sufficiently heavy damage can go unnoticed, as with any error
+/// correcting code. Symbols outside the alphabet are always rejected rather than repaired.
+///
+/// # Panics
+///
+/// Panics if `parity` is greater than `MAX_PARITY`.
+pub fn decode_with_parity<T: AsRef<str>>(input: T, parity: usize) -> Result<Repaired> {
+    assert!(parity <= MAX_PARITY, "too many parity symbols");
+
+    let input = input.as_ref();
+    let len = input.len();
+    if len <= parity {
+        return Err(Error::new(
+            Kind::InvalidLength,
+            "Encoded input is too short.",
+        ));
+    }
+    if len > MAX_ENCODED_LEN_U64 + parity {
+        return Err(Error::new(Kind::OutOfRange, "Encoded value is too large"));
+    }
+
+    let mut codeword = Vec::with_capacity(len);
+    for (idx, u) in input.bytes().enumerate() {
+        codeword.push(to_normal_digit(idx, u)?);
+    }
+
+    let repaired = correct(&mut codeword, parity)?;
+
+    let mut value: u64 = 0;
+    for &digit in &codeword[..len - parity] {
+        if value.leading_zeros() < 5 {
+            return Err(Error::new(Kind::OutOfRange, "Encoded value is too large"));
+        }
+        value = value << 5 | u64::from(digit);
+    }
+
+    Ok(Repaired { value, repaired })
+}
+
+fn mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        0
+    } else {
+        EXP[usize::from(LOG[usize::from(a)] + LOG[usize::from(b)])]
+    }
+}
+
+fn div(a: u8, b: u8) -> u8 {
+    debug_assert!(b != 0);
+    if a == 0 {
+        0
+    } else {
+        EXP[usize::from(LOG[usize::from(a)] + 31 - LOG[usize::from(b)])]
+    }
+}
+
+fn alpha_pow(i: usize) -> u8 {
+    EXP[i % 31]
+}
+
+/// Evaluates a polynomial, stored lowest degree first, at `x`.
+fn eval(poly: &[u8], x: u8) -> u8 {
+    poly.iter().rev().fold(0, |acc, &c| mul(acc, x) ^ c)
+}
+
+/// Computes parity symbols for the provided data symbols.
+fn parity_symbols(data: &[u8], parity: usize) -> Vec<u8> {
+    // The generator polynomial has roots at alpha^0 through alpha^(parity - 1). Coefficients are
+    // stored highest degree first, and the leading coefficient is always one.
+    let mut generator = vec![1];
+    for i in 0..parity {
+        let root = alpha_pow(i);
+        let mut next = generator.clone();
+        next.push(0);
+        for (j, &c) in generator.iter().enumerate() {
+            next[j + 1] ^= mul(c, root);
+        }
+        generator = next;
+    }
+
+    // Divide data * x^parity by the generator; the remainder is the parity.
+    let mut remainder = data.to_vec();
+    remainder.resize(data.len() + parity, 0);
+    for i in 0..data.len() {
+        let coefficient = remainder[i];
+        if coefficient != 0 {
+            for (j, &g) in generator.iter().enumerate().skip(1) {
+                remainder[i + j] ^= mul(g, coefficient);
+            }
+        }
+    }
+
+    remainder.split_off(data.len())
+}
+
+fn syndromes(codeword: &[u8], parity: usize) -> Vec<u8> {
+    (0..parity)
+        .map(|j| {
+            let x = alpha_pow(j);
+            codeword.iter().fold(0, |acc, &c| mul(acc, x) ^ c)
+        })
+        .collect()
+}
+
+/// Corrects a codeword in place, returning the indices of the repaired symbols.
+fn correct(codeword: &mut [u8], parity: usize) -> Result<Vec<usize>> {
+    let syndromes = syndromes(codeword, parity);
+    if syndromes.iter().all(|&s| s == 0) {
+        return Ok(Vec::new());
+    }
+
+    let locator = error_locator(&syndromes);
+    let errors = locator.len() - 1;
+    if 2 * errors > parity {
+        return Err(uncorrectable());
+    }
+
+    // The error evaluator is the product of the syndrome and locator polynomials, mod x^parity.
+    let mut evaluator = vec![0; parity];
+    for (i, &s) in syndromes.iter().enumerate() {
+        for (j, &l) in locator.iter().enumerate() {
+            if i + j < parity {
+                evaluator[i + j] ^= mul(s, l);
+            }
+        }
+    }
+
+    // In characteristic two, the formal derivative keeps only the odd-degree terms.
+    let derivative: Vec<u8> = locator
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, &c)| if i % 2 == 1 { c } else { 0 })
+        .collect();
+
+    let len = codeword.len();
+    let mut repaired = Vec::with_capacity(errors);
+    for (idx, symbol) in codeword.iter_mut().enumerate() {
+        let power = len - 1 - idx;
+        let location = alpha_pow(power);
+        let inverse = alpha_pow(31 - power % 31);
+
+        if eval(&locator, inverse) == 0 {
+            let denominator = eval(&derivative, inverse);
+            if denominator == 0 {
+                return Err(uncorrectable());
+            }
+
+            let magnitude = mul(location, div(eval(&evaluator, inverse), denominator));
+            *symbol ^= magnitude;
+            repaired.push(idx);
+        }
+    }
+
+    if repaired.len() != errors || syndromes_nonzero(codeword, parity) {
+        return Err(uncorrectable());
+    }
+
+    Ok(repaired)
+}
+
+fn syndromes_nonzero(codeword: &[u8], parity: usize) -> bool {
+    syndromes(codeword, parity).iter().any(|&s| s != 0)
+}
+
+/// Finds the error locator polynomial, lowest degree first, using Berlekamp-Massey.
+fn error_locator(syndromes: &[u8]) -> Vec<u8> {
+    let mut current = vec![1];
+    let mut previous = vec![1];
+    let mut degree = 0;
+    let mut shift = 1;
+    let mut last_discrepancy = 1;
+
+    for n in 0..syndromes.len() {
+        let discrepancy = (1..=degree)
+            .filter(|&i| i < current.len())
+            .fold(syndromes[n], |d, i| d ^ mul(current[i], syndromes[n - i]));
+
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+
+        let scale = div(discrepancy, last_discrepancy);
+        let mut next = current.clone();
+        if next.len() < previous.len() + shift {
+            next.resize(previous.len() + shift, 0);
+        }
+        for (i, &b) in previous.iter().enumerate() {
+            next[i + shift] ^= mul(scale, b);
+        }
+
+        if 2 * degree <= n {
+            previous = current;
+            degree = n + 1 - degree;
+            last_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+        current = next;
+    }
+
+    current.truncate(degree + 1);
+    current
+}
+
+fn uncorrectable() -> Error {
+    Error::new(
+        Kind::InvalidChecksum,
+        "Too many corrupted symbols to repair.",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EXP, LOG};
+    use crate::{
+        decode, decode_with_parity, encode_with_parity, error::Kind, Error, UPPERCASE_ENCODING,
+    };
+
+    fn corrupt(code: &str, positions: &[usize]) -> String {
+        code.bytes()
+            .enumerate()
+            .map(|(idx, u)| {
+                if positions.contains(&idx) {
+                    // Swap in a different symbol from the alphabet.
+                    let digit = UPPERCASE_ENCODING.iter().position(|&v| v == u).unwrap();
+                    char::from(UPPERCASE_ENCODING[(digit + 7) % 32])
+                } else {
+                    char::from(u)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tables_cover_the_field() {
+        for x in 1..32 {
+            assert_eq!(x, EXP[usize::from(LOG[x as usize])]);
+        }
+    }
+
+    #[test]
+    fn data_comes_first() {
+        let code = encode_with_parity(5111, 4);
+
+        assert!(code.starts_with("4ZQ"));
+        assert_eq!(Ok(5111), decode(&code[..3]));
+    }
+
+    #[test]
+    fn clean_codes_need_no_repair() {
+        for &n in &[0, 1, 5111, u64::MAX] {
+            for &parity in &[0, 1, 2, 4, 18] {
+                let repaired = decode_with_parity(encode_with_parity(n, parity), parity).unwrap();
+
+                assert_eq!(n, repaired.value);
+                assert!(repaired.repaired.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn single_errors_are_repaired_anywhere() {
+        let n = 0xDEAD_BEEF_CAFE;
+        let code = encode_with_parity(n, 2);
+
+        for idx in 0..code.len() {
+            let repaired = decode_with_parity(corrupt(&code, &[idx]), 2).unwrap();
+
+            assert_eq!(n, repaired.value);
+            assert_eq!(vec![idx], repaired.repaired);
+        }
+    }
+
+    #[test]
+    fn double_errors_are_repaired_anywhere() {
+        let n = u64::MAX;
+        let code = encode_with_parity(n, 4);
+
+        for a in 0..code.len() {
+            for b in a + 1..code.len() {
+                let repaired = decode_with_parity(corrupt(&code, &[a, b]), 4).unwrap();
+
+                assert_eq!(n, repaired.value);
+                assert_eq!(vec![a, b], repaired.repaired);
+            }
+        }
+    }
+
+    #[test]
+    fn too_many_errors_are_detected() {
+        let code = encode_with_parity(5111, 2);
+        let expected = Err(Error::new(Kind::InvalidChecksum, "Don't care"));
+
+        let mut detected = 0;
+        for a in 0..code.len() {
+            for b in a + 1..code.len() {
+                let result = decode_with_parity(corrupt(&code, &[a, b]), 2);
+                if result == expected {
+                    detected += 1;
+                }
+            }
+        }
+
+        // Two parity symbols cannot always tell two errors from one, but usually can.
+        assert!(detected > 0);
+    }
+
+    #[test]
+    fn short_input_fails() {
+        let expected = Err(Error::new(Kind::InvalidLength, "Don't care"));
+        assert_eq!(expected, decode_with_parity("AB", 2));
+    }
+
+    #[test]
+    fn lowercase_input_is_accepted() {
+        let code = encode_with_parity(5111, 2).to_lowercase();
+        assert_eq!(5111, decode_with_parity(code, 2).unwrap().value);
+    }
+}
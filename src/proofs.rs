@@ -0,0 +1,49 @@
+//! Proof harnesses, checked with `cargo kani`.
+//!
+//! Each harness covers every possible input rather than a sample of them: every `u64` for the
+//! encoding harnesses, and every byte sequence up to one byte longer than the longest valid
+//! encoding for the decoding harness.
+
+use crate::{decode, decoding::decode_raw, encode, encode_into, Write, MAX_ENCODED_LEN_U64};
+
+/// A buffer exactly as long as the longest encoding, so any write past it is caught.
+struct Bounded {
+    buf: [u8; MAX_ENCODED_LEN_U64],
+    len: usize,
+}
+
+impl Write for Bounded {
+    fn write(&mut self, u: u8) {
+        self.buf[self.len] = u;
+        self.len += 1;
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(15)]
+fn encode_stays_in_bounds() {
+    let n: u64 = kani::any();
+    let mut buf = Bounded {
+        buf: [0; MAX_ENCODED_LEN_U64],
+        len: 0,
+    };
+
+    encode_into(n, &mut buf);
+    assert!(buf.len >= 1 && buf.len <= MAX_ENCODED_LEN_U64);
+}
+
+#[kani::proof]
+#[kani::unwind(16)]
+fn decode_never_panics() {
+    let bytes: [u8; MAX_ENCODED_LEN_U64 + 1] = kani::any();
+    let len: usize = kani::any_where(|&len| len <= bytes.len());
+
+    let _ = decode_raw(&bytes[..len]);
+}
+
+#[kani::proof]
+#[kani::unwind(15)]
+fn decode_inverts_encode() {
+    let n: u64 = kani::any();
+    assert_eq!(Some(n), decode(encode(n)).ok());
+}
@@ -0,0 +1,500 @@
+use core::{fmt, str::FromStr};
+
+use crate::{
+    decoding::decode_padded,
+    encoding::{encode_padded, Digits},
+    Error, Result, MAX_ENCODED_LEN_U128,
+};
+
+const MAX_TIMESTAMP: u64 = 0xFFFF_FFFF_FFFF;
+const MAX_COUNTER: u32 = 0xFF_FFFF;
+
+/// A SCRU128 identifier, displayed as 26 Crockford digits.
+///
+/// From most to least significant, the fields are a 48-bit Unix timestamp in milliseconds, a
+/// 24-bit high counter, a 24-bit low counter and 32 bits of entropy. Identifiers from a single
+/// generator sort in the order they were created, even when many are created in the same
+/// millisecond.
+///
+/// Note that the reference implementation renders these in 25 base-36 digits; this type uses the
+/// crate's own alphabet instead.
+///
+/// ```rust
+/// # use crockford::{Error, Scru128Id};
+/// # fn run() -> Result<(), Error> {
+/// let id = Scru128Id::from_fields(1_571_000_000_000, 1, 2, 3);
+/// let encoded = id.to_string();
+///
+/// assert_eq!(26, encoded.len());
+/// assert_eq!(id, encoded.parse()?);
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Scru128Id(u128);
+
+impl Scru128Id {
+    /// Assembles an identifier from its fields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the timestamp does not fit in 48 bits or either counter does not fit in 24 bits.
+    pub fn from_fields(
+        timestamp: u64,
+        counter_hi: u32,
+        counter_lo: u32,
+        entropy: u32,
+    ) -> Scru128Id {
+        assert!(timestamp <= MAX_TIMESTAMP, "timestamp must fit in 48 bits");
+        assert!(
+            counter_hi <= MAX_COUNTER && counter_lo <= MAX_COUNTER,
+            "counters must fit in 24 bits"
+        );
+
+        Scru128Id(
+            u128::from(timestamp) << 80
+                | u128::from(counter_hi) << 56
+                | u128::from(counter_lo) << 32
+                | u128::from(entropy),
+        )
+    }
+
+    /// Interprets a 128-bit value as an identifier.
+    pub fn from_u128(n: u128) -> Scru128Id {
+        Scru128Id(n)
+    }
+
+    /// Returns the identifier as a 128-bit value.
+    pub fn as_u128(self) -> u128 {
+        self.0
+    }
+
+    /// Returns the timestamp, in milliseconds since the Unix epoch.
+    pub fn timestamp(self) -> u64 {
+        (self.0 >> 80) as u64
+    }
+
+    /// Returns the high counter.
+    pub fn counter_hi(self) -> u32 {
+        (self.0 >> 56) as u32 & MAX_COUNTER
+    }
+
+    /// Returns the low counter.
+    pub fn counter_lo(self) -> u32 {
+        (self.0 >> 32) as u32 & MAX_COUNTER
+    }
+
+    /// Returns the entropy.
+    pub fn entropy(self) -> u32 {
+        self.0 as u32
+    }
+}
+
+impl fmt::Display for Scru128Id {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut digits = Digits::<MAX_ENCODED_LEN_U128>::new();
+        encode_padded(self.0, MAX_ENCODED_LEN_U128, &mut digits);
+        f.pad(digits.as_str())
+    }
+}
+
+impl FromStr for Scru128Id {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Scru128Id> {
+        decode_padded(s, MAX_ENCODED_LEN_U128).map(Scru128Id)
+    }
+}
+
+#[cfg(feature = "rand")]
+pub use self::generator::{Scru128Generator, Scru128State};
+
+#[cfg(feature = "rand")]
+mod generator {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use rand::{rngs::ThreadRng, RngCore};
+
+    use super::{Scru128Id, MAX_COUNTER, MAX_TIMESTAMP};
+    use crate::{error::Kind, Error, Result};
+
+    /// How far the clock may move backwards before the generator gives up on monotonicity.
+    const ROLLBACK_ALLOWANCE: u64 = 10_000;
+
+    /// How often, in milliseconds, the high counter is refreshed with random bits.
+    const COUNTER_HI_INTERVAL: u64 = 1_000;
+
+    /// Generates monotonically increasing SCRU128 identifiers.
+    ///
+    /// This follows the reference algorithm: within a millisecond the low counter increments,
+    /// overflowing into the high counter and then into the timestamp, so identifiers keep
+    /// increasing however many are generated at once. If the system clock moves backwards by more
+    /// than ten seconds, or the counters overflow at the largest possible timestamp, the
+    /// generator resets and monotonicity is lost.
+    ///
+    /// Unlike `Uuid7Generator` and `Cuid2Generator`, generating takes `&mut self`: the timestamp
+    /// and both counters take 96 bits, too many to advance with a single atomic, so sharing a
+    /// generator between threads still needs a `Mutex`.
+    ///
+    /// ```rust
+    /// use crockford::Scru128Generator;
+    ///
+    /// let mut generator = Scru128Generator::new();
+    /// let first = generator.generate();
+    /// let second = generator.generate();
+    ///
+    /// assert!(first < second);
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct Scru128Generator<R = ThreadRng> {
+        timestamp: u64,
+        counter_hi: u32,
+        counter_lo: u32,
+        ts_counter_hi: u64,
+        rng: R,
+    }
+
+    /// A snapshot of a generator's counters, for carrying it across a restart.
+    ///
+    /// A generator resumed from the state left by the last identifier it issued carries on with
+    /// strictly greater identifiers, provided the clock has not since moved backwards by more
+    /// than the generator tolerates.
+    ///
+    /// ```rust
+    /// use crockford::Scru128Generator;
+    ///
+    /// let mut generator = Scru128Generator::new();
+    /// let last = generator.generate();
+    /// let state = generator.state();
+    ///
+    /// // Later, perhaps in another process...
+    /// let mut generator = Scru128Generator::resume(state).unwrap();
+    /// assert!(last < generator.generate());
+    /// ```
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Scru128State {
+        pub(super) timestamp: u64,
+        pub(super) counter_hi: u32,
+        pub(super) counter_lo: u32,
+        pub(super) ts_counter_hi: u64,
+    }
+
+    impl Scru128Generator {
+        /// Creates a generator that uses the thread-local random number generator.
+        pub fn new() -> Scru128Generator {
+            Scru128Generator::with_rng(rand::thread_rng())
+        }
+
+        /// Creates a generator that picks up where a previous one left off, using the
+        /// thread-local random number generator.
+        ///
+        /// Fails if the state could not have been left by a generator; see `resume_with_rng`.
+        pub fn resume(state: Scru128State) -> Result<Scru128Generator> {
+            Scru128Generator::resume_with_rng(state, rand::thread_rng())
+        }
+    }
+
+    impl Default for Scru128Generator {
+        fn default() -> Scru128Generator {
+            Scru128Generator::new()
+        }
+    }
+
+    impl<R: RngCore> Scru128Generator<R> {
+        /// Creates a generator that uses the provided random number generator.
+        pub fn with_rng(rng: R) -> Scru128Generator<R> {
+            Scru128Generator {
+                timestamp: 0,
+                counter_hi: 0,
+                counter_lo: 0,
+                ts_counter_hi: 0,
+                rng,
+            }
+        }
+
+        /// Creates a generator that picks up where a previous one left off, using the provided
+        /// random number generator.
+        ///
+        /// States may come from storage or over the wire, so they are checked first. This fails
+        /// with `InvalidFormat` if the timestamp does not fit in 48 bits, either counter does not
+        /// fit in 24 bits, or the high counter was refreshed after the timestamp.
+        pub fn resume_with_rng(state: Scru128State, rng: R) -> Result<Scru128Generator<R>> {
+            if state.timestamp > MAX_TIMESTAMP
+                || state.counter_hi > MAX_COUNTER
+                || state.counter_lo > MAX_COUNTER
+                || state.ts_counter_hi > state.timestamp
+            {
+                return Err(Error::new(
+                    Kind::InvalidFormat,
+                    "Generator state is inconsistent.",
+                ));
+            }
+
+            Ok(Scru128Generator {
+                timestamp: state.timestamp,
+                counter_hi: state.counter_hi,
+                counter_lo: state.counter_lo,
+                ts_counter_hi: state.ts_counter_hi,
+                rng,
+            })
+        }
+
+        /// Returns a snapshot of the generator's counters.
+        pub fn state(&self) -> Scru128State {
+            Scru128State {
+                timestamp: self.timestamp,
+                counter_hi: self.counter_hi,
+                counter_lo: self.counter_lo,
+                ts_counter_hi: self.ts_counter_hi,
+            }
+        }
+
+        /// Generates an identifier for the current time.
+        pub fn generate(&mut self) -> Scru128Id {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_millis() as u64)
+                .unwrap_or(1);
+            self.generate_at(timestamp)
+        }
+
+        /// Generates an identifier for the provided time, in milliseconds since the Unix epoch.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the timestamp is zero or does not fit in 48 bits.
+        pub fn generate_at(&mut self, timestamp: u64) -> Scru128Id {
+            assert!(
+                timestamp > 0 && timestamp <= MAX_TIMESTAMP,
+                "timestamp must be nonzero and fit in 48 bits"
+            );
+
+            if timestamp > self.timestamp {
+                self.timestamp = timestamp;
+                self.counter_lo = self.rng.next_u32() & MAX_COUNTER;
+            } else if timestamp + ROLLBACK_ALLOWANCE >= self.timestamp {
+                // Carry on with the previous timestamp while the clock catches up.
+                self.counter_lo += 1;
+                if self.counter_lo > MAX_COUNTER {
+                    self.counter_lo = 0;
+                    self.counter_hi += 1;
+                    if self.counter_hi > MAX_COUNTER {
+                        self.counter_hi = 0;
+                        match self
+                            .timestamp
+                            .checked_add(1)
+                            .filter(|&t| t <= MAX_TIMESTAMP)
+                        {
+                            Some(timestamp) => self.timestamp = timestamp,
+                            // Out of timestamps; start over with fresh counters.
+                            None => self.ts_counter_hi = 0,
+                        }
+                        self.counter_lo = self.rng.next_u32() & MAX_COUNTER;
+                    }
+                }
+            } else {
+                // The clock went too far backwards; start over.
+                self.timestamp = timestamp;
+                self.ts_counter_hi = 0;
+                self.counter_lo = self.rng.next_u32() & MAX_COUNTER;
+            }
+
+            if self.ts_counter_hi == 0
+                || self.timestamp.saturating_sub(self.ts_counter_hi) >= COUNTER_HI_INTERVAL
+            {
+                self.ts_counter_hi = self.timestamp;
+                self.counter_hi = self.rng.next_u32() & MAX_COUNTER;
+            }
+
+            Scru128Id::from_fields(
+                self.timestamp,
+                self.counter_hi,
+                self.counter_lo,
+                self.rng.next_u32(),
+            )
+        }
+    }
+
+    /// Yields identifiers for the current time, indefinitely.
+    impl<R: RngCore> Iterator for Scru128Generator<R> {
+        type Item = Scru128Id;
+
+        fn next(&mut self) -> Option<Scru128Id> {
+            Some(self.generate())
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (usize::MAX, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Scru128Id;
+
+    #[test]
+    fn fields_round_trip() {
+        let id = Scru128Id::from_fields(0xFFFF_FFFF_FFFF, 0xFF_FFFF, 0x12_3456, 0xDEAD_BEEF);
+
+        assert_eq!(0xFFFF_FFFF_FFFF, id.timestamp());
+        assert_eq!(0xFF_FFFF, id.counter_hi());
+        assert_eq!(0x12_3456, id.counter_lo());
+        assert_eq!(0xDEAD_BEEF, id.entropy());
+    }
+
+    #[test]
+    fn encoded_form_round_trips() {
+        let id = Scru128Id::from_u128(u128::MAX);
+
+        assert_eq!("7ZZZZZZZZZZZZZZZZZZZZZZZZZ", id.to_string());
+        assert_eq!(Ok(id), "7zzzzzzzzzzzzzzzzzzzzzzzzz".parse());
+    }
+
+    #[test]
+    #[should_panic]
+    fn oversized_counters_panic() {
+        Scru128Id::from_fields(0, 0x100_0000, 0, 0);
+    }
+
+    #[cfg(feature = "rand")]
+    mod generator {
+        use rand::rngs::mock::StepRng;
+
+        use super::super::{MAX_COUNTER, MAX_TIMESTAMP};
+        use crate::{error::Kind, Error, Scru128Generator, Scru128State};
+
+        #[test]
+        fn ids_increase_within_a_millisecond() {
+            let mut generator = Scru128Generator::new();
+            let mut previous = generator.generate_at(1000);
+
+            for _ in 0..10_000 {
+                let id = generator.generate_at(1000);
+                assert!(previous < id);
+                assert!(previous.to_string() < id.to_string());
+                previous = id;
+            }
+        }
+
+        #[test]
+        fn small_rollbacks_keep_the_previous_timestamp() {
+            let mut generator = Scru128Generator::new();
+            let first = generator.generate_at(20_000);
+            let second = generator.generate_at(15_000);
+
+            assert!(first < second);
+            assert_eq!(20_000, second.timestamp());
+        }
+
+        #[test]
+        fn large_rollbacks_reset() {
+            let mut generator = Scru128Generator::new();
+            generator.generate_at(20_000);
+
+            assert_eq!(5_000, generator.generate_at(5_000).timestamp());
+        }
+
+        #[test]
+        fn generators_are_iterators() {
+            let ids: Vec<_> = Scru128Generator::new().take(100).collect();
+
+            assert_eq!(100, ids.len());
+            assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+
+        #[test]
+        fn resumed_generators_keep_increasing() {
+            let mut generator = Scru128Generator::new();
+            let last = generator.generate_at(1000);
+
+            let mut resumed = Scru128Generator::resume(generator.state()).unwrap();
+            assert!(last < resumed.generate_at(1000));
+
+            // Even when the clock reads a little earlier after the restart.
+            let mut resumed = Scru128Generator::resume(generator.state()).unwrap();
+            assert!(last < resumed.generate_at(900));
+        }
+
+        #[test]
+        fn inconsistent_states_are_rejected() {
+            let valid = Scru128State {
+                timestamp: 1000,
+                counter_hi: 0,
+                counter_lo: 0,
+                ts_counter_hi: 1000,
+            };
+            let invalid = [
+                Scru128State {
+                    ts_counter_hi: 1001,
+                    ..valid
+                },
+                Scru128State {
+                    timestamp: MAX_TIMESTAMP + 1,
+                    ..valid
+                },
+                Scru128State {
+                    counter_hi: MAX_COUNTER + 1,
+                    ..valid
+                },
+                Scru128State {
+                    counter_lo: MAX_COUNTER + 1,
+                    ..valid
+                },
+            ];
+
+            assert!(Scru128Generator::resume(valid).is_ok());
+            for &state in &invalid {
+                assert_eq!(
+                    Err(Error::new(Kind::InvalidFormat, "Don't care")),
+                    Scru128Generator::resume(state).map(|_| ()),
+                    "{:?}",
+                    state
+                );
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn state_round_trips_through_serde() {
+            let mut generator = Scru128Generator::new();
+            generator.generate_at(1000);
+            let state = generator.state();
+
+            let json = serde_json::to_string(&state).unwrap();
+            assert_eq!(state, serde_json::from_str::<Scru128State>(&json).unwrap());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn deserialized_states_are_checked_on_resume() {
+            let json = r#"{"timestamp":5,"counter_hi":0,"counter_lo":0,"ts_counter_hi":1000}"#;
+            let state = serde_json::from_str::<Scru128State>(json).unwrap();
+
+            assert!(Scru128Generator::resume(state).is_err());
+        }
+
+        #[test]
+        fn counter_overflow_advances_the_timestamp() {
+            // Every random draw is the maximum, so the counters start out full.
+            let mut generator = Scru128Generator::with_rng(StepRng::new(u64::MAX, 0));
+            let first = generator.generate_at(1000);
+            let second = generator.generate_at(1000);
+
+            assert_eq!(1000, first.timestamp());
+            assert_eq!(1001, second.timestamp());
+            assert!(first < second);
+        }
+
+        #[test]
+        fn counter_overflow_at_the_last_timestamp_starts_over() {
+            let mut generator = Scru128Generator::with_rng(StepRng::new(u64::MAX, 0));
+            generator.generate_at(MAX_TIMESTAMP);
+            let id = generator.generate_at(MAX_TIMESTAMP);
+
+            assert_eq!(MAX_TIMESTAMP, id.timestamp());
+        }
+    }
+}
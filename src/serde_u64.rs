@@ -0,0 +1,144 @@
+//! Serializes `u64` fields as Crockford strings, for use with `#[serde(with = "...")]`.
+//!
+//! Values are written as they would be by `encode` and read back as they would be by `decode`,
+//! so input is case-insensitive and ambiguous symbols are folded. The `check` submodule does the
+//! same with a trailing check symbol.
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct User {
+//!     #[serde(with = "crockford::serde_u64")]
+//!     id: u64,
+//!     #[serde(with = "crockford::serde_u64::check")]
+//!     invite: u64,
+//! }
+//!
+//! let json = serde_json::to_string(&User { id: 5111, invite: 5111 }).unwrap();
+//! assert_eq!(r#"{"id":"4ZQ","invite":"4ZQ5"}"#, json);
+//!
+//! let user: User = serde_json::from_str(r#"{"id":"4zq","invite":"4zq5"}"#).unwrap();
+//! assert_eq!((5111, 5111), (user.id, user.invite));
+//! ```
+
+use std::fmt;
+
+use serde::{de, Deserializer, Serializer};
+
+use crate::{decode, display, Result};
+
+/// Serializes a `u64` as its Crockford encoding.
+pub fn serialize<S: Serializer>(n: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&display(*n))
+}
+
+/// Deserializes a `u64` from its Crockford encoding.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    deserializer.deserialize_str(Visitor {
+        decode: |s| decode(s),
+        expecting: "a Crockford-encoded u64",
+    })
+}
+
+/// Serializes `u64` fields as Crockford strings with a trailing check symbol.
+pub mod check {
+    use serde::{Deserializer, Serializer};
+
+    use super::Visitor;
+    use crate::{decode_with_check, encode_with_check, Result};
+
+    /// Serializes a `u64` as its Crockford encoding followed by its check symbol.
+    pub fn serialize<S: Serializer>(n: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&encode_with_check(*n))
+    }
+
+    /// Deserializes a `u64` from its Crockford encoding, verifying and removing the check symbol.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_str(Visitor {
+            decode: |s| decode_with_check(s),
+            expecting: "a Crockford-encoded u64 with a check symbol",
+        })
+    }
+}
+
+struct Visitor {
+    decode: fn(&str) -> Result<u64>,
+    expecting: &'static str,
+}
+
+impl de::Visitor<'_> for Visitor {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<u64, E> {
+        (self.decode)(v).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Ids {
+        #[serde(with = "crate::serde_u64")]
+        plain: u64,
+        #[serde(with = "crate::serde_u64::check")]
+        checked: u64,
+    }
+
+    #[test]
+    fn values_round_trip() {
+        for &n in &[0, 1, 5111, u64::MAX] {
+            let ids = Ids {
+                plain: n,
+                checked: n,
+            };
+            let json = serde_json::to_string(&ids).unwrap();
+            assert_eq!(ids, serde_json::from_str(&json).unwrap());
+        }
+    }
+
+    #[test]
+    fn values_serialize_as_strings() {
+        let ids = Ids {
+            plain: 5111,
+            checked: 36,
+        };
+
+        assert_eq!(
+            r#"{"plain":"4ZQ","checked":"14U"}"#,
+            serde_json::to_string(&ids).unwrap()
+        );
+    }
+
+    #[test]
+    fn bad_values_fail_to_deserialize() {
+        let bad = [
+            r#"{"plain":"4Z!","checked":"4ZQ5"}"#,
+            r#"{"plain":"4ZQ","checked":"4ZQ6"}"#,
+            r#"{"plain":5111,"checked":"4ZQ5"}"#,
+        ];
+
+        for json in &bad {
+            assert!(serde_json::from_str::<Ids>(json).is_err(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn non_ascii_values_fail_to_deserialize() {
+        let bad = [
+            r#"{"plain":"4ZQé","checked":"4ZQ5"}"#,
+            r#"{"plain":"4ZQ","checked":"4ZQé"}"#,
+            r#"{"plain":"4ZQ","checked":"é"}"#,
+        ];
+
+        for json in &bad {
+            assert!(serde_json::from_str::<Ids>(json).is_err(), "{}", json);
+        }
+    }
+}
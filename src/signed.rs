@@ -0,0 +1,198 @@
+//! Tamper-evident tokens of the form `payload.signature`.
+//!
+//! The payload is the ordinary encoding of a `u64` value. The signature is an HMAC-SHA256 of the
+//! value, truncated to 128 bits and encoded as exactly twenty-six digits. Verification compares
+//! signatures in constant time.
+//!
+//! A `Keyring` signs with its primary key and accepts tokens signed by any of its keys, so keys
+//! can be rotated without invalidating tokens that are already in circulation.
+//!
+//! ```rust
+//! # use crockford::Error;
+//! use crockford::signed::Keyring;
+//!
+//! # fn run() -> Result<(), Error> {
+//! let mut keyring = Keyring::new(b"old secret");
+//! let token = keyring.sign(5111);
+//! assert!(token.starts_with("4ZQ."));
+//!
+//! keyring.rotate(b"new secret");
+//! assert_eq!(5111, keyring.verify(&token)?);
+//! assert_ne!(token, keyring.sign(5111));
+//! # Ok(())
+//! # }
+//! # run().unwrap()
+//! ```
+
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{
+    decode, decoding::decode_padded, encode_into, encoding::encode_padded, error::Kind, Error,
+    Result, MAX_ENCODED_LEN_U128, MAX_ENCODED_LEN_U64,
+};
+
+pub(crate) type HmacSha256 = Hmac<Sha256>;
+
+/// Separates the payload from the signature.
+const SEPARATOR: char = '.';
+
+/// A set of keys for signing and verifying tokens.
+#[derive(Clone)]
+pub struct Keyring {
+    // The primary key comes first.
+    keys: Vec<HmacSha256>,
+}
+
+impl Keyring {
+    /// Creates a keyring with a single primary key.
+    pub fn new(key: &[u8]) -> Keyring {
+        Keyring {
+            keys: vec![mac_for(key)],
+        }
+    }
+
+    /// Adds a key that is accepted for verification but never used for signing.
+    pub fn with_previous(mut self, key: &[u8]) -> Keyring {
+        self.keys.push(mac_for(key));
+        self
+    }
+
+    /// Makes the provided key primary, keeping the current keys for verification only.
+    pub fn rotate(&mut self, key: &[u8]) {
+        self.keys.insert(0, mac_for(key));
+    }
+
+    /// Drops every key except the primary, so tokens signed by older keys stop verifying.
+    pub fn retire_previous(&mut self) {
+        self.keys.truncate(1);
+    }
+
+    /// Creates a signed token for the provided value using the primary key.
+    pub fn sign(&self, n: u64) -> String {
+        let truncated = self.sign_bytes(&n.to_be_bytes());
+
+        let mut token = String::with_capacity(MAX_ENCODED_LEN_U64 + 1 + MAX_ENCODED_LEN_U128);
+        encode_into(n, &mut token);
+        token.push(SEPARATOR);
+        encode_padded(
+            u128::from_be_bytes(truncated),
+            MAX_ENCODED_LEN_U128,
+            &mut token,
+        );
+        token
+    }
+
+    /// Attempts to verify a signed token, returning the value it carries.
+    ///
+    /// Fails if the token is malformed or if no key in the keyring produced its signature.
+    pub fn verify<T: AsRef<str>>(&self, token: T) -> Result<u64> {
+        let token = token.as_ref();
+        let idx = token
+            .find(SEPARATOR)
+            .ok_or_else(|| Error::new(Kind::InvalidSignature, "Token has no signature."))?;
+
+        let n = decode(&token[..idx])?;
+        let signature = decode_padded(&token[idx + 1..], MAX_ENCODED_LEN_U128)?.to_be_bytes();
+
+        if self.verify_bytes(&n.to_be_bytes(), &signature) {
+            Ok(n)
+        } else {
+            Err(Error::new(
+                Kind::InvalidSignature,
+                "Token signature is invalid.",
+            ))
+        }
+    }
+
+    /// Signs arbitrary bytes with the primary key, truncating the MAC to 128 bits.
+    pub(crate) fn sign_bytes(&self, bytes: &[u8]) -> [u8; 16] {
+        let signature = self.keys[0].clone().chain_update(bytes).finalize();
+        let mut truncated = [0; 16];
+        truncated.copy_from_slice(&signature.into_bytes()[..16]);
+        truncated
+    }
+
+    /// Checks a truncated signature against every key in the keyring.
+    pub(crate) fn verify_bytes(&self, bytes: &[u8], signature: &[u8]) -> bool {
+        self.keys.iter().any(|key| {
+            key.clone()
+                .chain_update(bytes)
+                .verify_truncated_left(signature)
+                .is_ok()
+        })
+    }
+}
+
+impl fmt::Debug for Keyring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Never print key material.
+        f.debug_struct("Keyring")
+            .field("keys", &self.keys.len())
+            .finish()
+    }
+}
+
+pub(crate) fn mac_for(key: &[u8]) -> HmacSha256 {
+    HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Keyring;
+    use crate::{error::Kind, Error};
+
+    #[test]
+    fn tokens_round_trip() {
+        let keyring = Keyring::new(b"secret");
+
+        for &n in &[0, 5111, u64::MAX] {
+            let token = keyring.sign(n);
+            assert_eq!(Ok(n), keyring.verify(&token));
+            assert_eq!(Ok(n), keyring.verify(token.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn signatures_have_fixed_width() {
+        let token = Keyring::new(b"secret").sign(0);
+        assert_eq!("0.".len() + 26, token.len());
+    }
+
+    #[test]
+    fn tampered_payloads_fail() {
+        let keyring = Keyring::new(b"secret");
+        let token = keyring.sign(5111);
+        let tampered = token.replacen("4ZQ", "4ZR", 1);
+        let expected = Err(Error::new(Kind::InvalidSignature, "Don't care"));
+
+        assert_eq!(expected, keyring.verify(tampered));
+    }
+
+    #[test]
+    fn foreign_keys_fail() {
+        let token = Keyring::new(b"secret").sign(5111);
+        let expected = Err(Error::new(Kind::InvalidSignature, "Don't care"));
+
+        assert_eq!(expected, Keyring::new(b"other").verify(token));
+    }
+
+    #[test]
+    fn previous_keys_verify_until_retired() {
+        let token = Keyring::new(b"old").sign(5111);
+        let mut keyring = Keyring::new(b"new").with_previous(b"old");
+
+        assert_eq!(Ok(5111), keyring.verify(&token));
+
+        keyring.retire_previous();
+        assert!(keyring.verify(&token).is_err());
+    }
+
+    #[test]
+    fn unsigned_tokens_fail() {
+        let expected = Err(Error::new(Kind::InvalidSignature, "Don't care"));
+        assert_eq!(expected, Keyring::new(b"secret").verify("4ZQ"));
+    }
+}
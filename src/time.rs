@@ -0,0 +1,148 @@
+use std::{
+    convert::TryFrom,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{decode, encode, error::Kind, Error, Result};
+
+/// The unit in which durations and timestamps are counted before encoding.
+///
+/// Anything finer than the chosen resolution is truncated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    /// Whole seconds.
+    Seconds,
+    /// Whole milliseconds.
+    Millis,
+    /// Whole microseconds.
+    Micros,
+}
+
+impl Resolution {
+    fn count(self, duration: Duration) -> Result<u64> {
+        let count = match self {
+            Resolution::Seconds => u128::from(duration.as_secs()),
+            Resolution::Millis => duration.as_millis(),
+            Resolution::Micros => duration.as_micros(),
+        };
+
+        u64::try_from(count)
+            .map_err(|_| Error::new(Kind::OutOfRange, "Duration is too long to encode"))
+    }
+
+    fn duration(self, count: u64) -> Duration {
+        match self {
+            Resolution::Seconds => Duration::from_secs(count),
+            Resolution::Millis => Duration::from_millis(count),
+            Resolution::Micros => Duration::from_micros(count),
+        }
+    }
+}
+
+/// Encodes a `Duration` as a count of the provided unit.
+///
+/// Fails if the count does not fit in a `u64`, which only happens for durations of hundreds of
+/// thousands of years.
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use crockford::{Error, Resolution};
+/// # fn run() -> Result<(), Error> {
+/// let elapsed = Duration::from_millis(5111);
+///
+/// assert_eq!("4ZQ", crockford::encode_duration(elapsed, Resolution::Millis)?);
+/// assert_eq!("5", crockford::encode_duration(elapsed, Resolution::Seconds)?);
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+pub fn encode_duration(duration: Duration, resolution: Resolution) -> Result<String> {
+    resolution.count(duration).map(encode)
+}
+
+/// Decodes a `Duration` encoded as a count of the provided unit.
+pub fn decode_duration<T: AsRef<str>>(input: T, resolution: Resolution) -> Result<Duration> {
+    decode(input).map(|count| resolution.duration(count))
+}
+
+/// Encodes a `SystemTime` as a count of the provided unit since the Unix epoch.
+///
+/// Fails for times before the epoch.
+pub fn encode_system_time(time: SystemTime, resolution: Resolution) -> Result<String> {
+    let since_epoch = time
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| Error::new(Kind::OutOfRange, "Time is before the Unix epoch"))?;
+    encode_duration(since_epoch, resolution)
+}
+
+/// Decodes a `SystemTime` encoded as a count of the provided unit since the Unix epoch.
+///
+/// Fails if the time cannot be represented by the platform's `SystemTime`.
+pub fn decode_system_time<T: AsRef<str>>(input: T, resolution: Resolution) -> Result<SystemTime> {
+    let since_epoch = decode_duration(input, resolution)?;
+    UNIX_EPOCH
+        .checked_add(since_epoch)
+        .ok_or_else(|| Error::new(Kind::OutOfRange, "Time is out of range for this platform"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::{
+        decode_duration, decode_system_time, encode_duration, encode_system_time, error::Kind,
+        Error, Resolution,
+    };
+
+    #[test]
+    fn durations_round_trip() {
+        let duration = Duration::from_micros(1_234_567_890);
+
+        for &resolution in &[Resolution::Seconds, Resolution::Millis, Resolution::Micros] {
+            let encoded = encode_duration(duration, resolution).unwrap();
+            let decoded = decode_duration(&encoded, resolution).unwrap();
+
+            assert!(decoded <= duration);
+            assert_eq!(encoded, encode_duration(decoded, resolution).unwrap());
+        }
+    }
+
+    #[test]
+    fn durations_are_truncated() {
+        let duration = Duration::from_millis(1999);
+        let expected = Ok(Duration::from_secs(1));
+        let actual = decode_duration(
+            encode_duration(duration, Resolution::Seconds).unwrap(),
+            Resolution::Seconds,
+        );
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn long_durations_fail() {
+        let duration = Duration::from_secs(u64::MAX);
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+        let actual = encode_duration(duration, Resolution::Millis);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn system_times_round_trip() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_571_000_000_123);
+        let encoded = encode_system_time(time, Resolution::Millis).unwrap();
+
+        assert_eq!("1DQ3EB7KV", encoded);
+        assert_eq!(Ok(time), decode_system_time(&encoded, Resolution::Millis));
+    }
+
+    #[test]
+    fn times_before_the_epoch_fail() {
+        let time = UNIX_EPOCH - Duration::from_secs(1);
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+        let actual = encode_system_time(time, Resolution::Seconds);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,219 @@
+use alloc::string::String;
+
+use crate::{
+    decoding::decode_padded, encoding::encode_padded, error::Kind, Error, Result,
+    MAX_ENCODED_LEN_U32, UPPERCASE_ENCODING,
+};
+
+/// Describes a family of secret tokens that secret scanners can recognize reliably.
+///
+/// Tokens look like `{prefix}_{body}{crc}`: a fixed prefix, an underscore, a fixed number of
+/// random uppercase symbols, and a seven-digit CRC-32 of everything before it. The fixed shape
+/// makes tokens easy to match with a pattern, and the CRC lets a scanner discard almost every
+/// false positive without contacting the issuer.
+///
+/// ```rust
+/// use crockford::TokenProfile;
+///
+/// const API_KEY: TokenProfile = TokenProfile::new("acme", 24);
+///
+/// let token = API_KEY.issue_from(&[7; 24]);
+///
+/// assert_eq!(API_KEY.len(), token.len());
+/// assert!(API_KEY.looks_like_token(&token));
+/// assert!(API_KEY.verify_structure(&token).is_ok());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TokenProfile {
+    prefix: &'static str,
+    body_len: usize,
+}
+
+impl TokenProfile {
+    /// Creates a profile for tokens with the provided prefix and number of random symbols.
+    pub const fn new(prefix: &'static str, body_len: usize) -> TokenProfile {
+        TokenProfile { prefix, body_len }
+    }
+
+    /// Returns the length of every token issued under this profile.
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.prefix.len() + 1 + self.body_len + MAX_ENCODED_LEN_U32
+    }
+
+    /// Issues a token using one byte of caller-provided entropy per random symbol.
+    ///
+    /// Only the low five bits of each byte are used, so uniformly random bytes produce uniformly
+    /// random symbols.
+    ///
+    /// # Panics
+    ///
+    /// Panics if fewer bytes are provided than the profile has random symbols.
+    pub fn issue_from(&self, entropy: &[u8]) -> String {
+        assert!(
+            entropy.len() >= self.body_len,
+            "not enough entropy for token body"
+        );
+
+        let mut token = String::with_capacity(self.len());
+        token.push_str(self.prefix);
+        token.push('_');
+        token.extend(
+            entropy[..self.body_len]
+                .iter()
+                .map(|&u| char::from(UPPERCASE_ENCODING[usize::from(u & 0x1F)])),
+        );
+
+        let crc = crc32(token.as_bytes());
+        encode_padded(crc.into(), MAX_ENCODED_LEN_U32, &mut token);
+        token
+    }
+
+    /// Issues a token with a random body.
+    #[cfg(feature = "rand")]
+    pub fn issue<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let mut entropy = vec![0; self.body_len];
+        rng.fill_bytes(&mut entropy);
+        self.issue_from(&entropy)
+    }
+
+    /// Checks, without verifying the CRC, whether a string has the shape of a token.
+    ///
+    /// Tokens are always written in canonical uppercase, so nothing else is accepted.
+    pub fn looks_like_token(&self, s: &str) -> bool {
+        s.len() == self.len()
+            && s.starts_with(self.prefix)
+            && s.as_bytes()[self.prefix.len()] == b'_'
+            && s.as_bytes()[self.prefix.len() + 1..]
+                .iter()
+                .all(|u| UPPERCASE_ENCODING.contains(u))
+    }
+
+    /// Verifies that a string has the shape of a token and that its CRC matches.
+    ///
+    /// This says nothing about whether the token was actually issued, only that it was not
+    /// mistyped or made up.
+    ///
+    /// Input of the wrong length fails with `InvalidLength`, and input with the wrong prefix or
+    /// symbols fails with `InvalidFormat`.
+    pub fn verify_structure(&self, s: &str) -> Result<()> {
+        if s.len() != self.len() {
+            return Err(Error::new(
+                Kind::InvalidLength,
+                "Input does not have the length of a token.",
+            ));
+        }
+        if !self.looks_like_token(s) {
+            return Err(Error::new(
+                Kind::InvalidFormat,
+                "Input does not have the shape of a token.",
+            ));
+        }
+
+        let split = s.len() - MAX_ENCODED_LEN_U32;
+        let expected = decode_padded(&s[split..], MAX_ENCODED_LEN_U32)?;
+
+        if u128::from(crc32(&s.as_bytes()[..split])) == expected {
+            Ok(())
+        } else {
+            Err(Error::new(
+                Kind::InvalidChecksum,
+                "Token checksum does not match.",
+            ))
+        }
+    }
+}
+
+/// Computes the IEEE CRC-32 of the provided bytes.
+fn crc32(bytes: &[u8]) -> u32 {
+    const POLYNOMIAL: u32 = 0xEDB8_8320;
+
+    let mut crc = !0;
+    for &u in bytes {
+        crc ^= u32::from(u);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                crc >> 1 ^ POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::crc32;
+    use crate::{error::Kind, Error, TokenProfile};
+
+    const PROFILE: TokenProfile = TokenProfile::new("test", 20);
+
+    fn token() -> String {
+        let entropy: Vec<u8> = (0..20).collect();
+        PROFILE.issue_from(&entropy)
+    }
+
+    #[test]
+    fn crc32_matches_reference() {
+        assert_eq!(0xCBF4_3926, crc32(b"123456789"));
+    }
+
+    #[test]
+    fn tokens_have_expected_shape() {
+        let token = token();
+
+        assert_eq!(32, token.len());
+        assert!(token.starts_with("test_0123456789ABCDEFGHJK"));
+        assert!(PROFILE.looks_like_token(&token));
+        assert_eq!(Ok(()), PROFILE.verify_structure(&token));
+    }
+
+    #[test]
+    fn lowercase_tokens_are_rejected() {
+        let token = token().to_lowercase();
+        assert!(!PROFILE.looks_like_token(&token));
+    }
+
+    #[test]
+    fn wrong_prefix_or_length_is_rejected() {
+        let token = token();
+
+        assert!(!PROFILE.looks_like_token(&token.replace("test", "tset")));
+        assert!(!PROFILE.looks_like_token(&token[1..]));
+        assert!(!TokenProfile::new("test", 21).looks_like_token(&token));
+    }
+
+    #[test]
+    fn structure_errors_tell_length_from_shape() {
+        let token = token();
+
+        assert_eq!(
+            Err(Error::new(Kind::InvalidLength, "Don't care")),
+            PROFILE.verify_structure(&token[1..])
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidFormat, "Don't care")),
+            PROFILE.verify_structure(&token.replace("test", "tset"))
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidFormat, "Don't care")),
+            PROFILE.verify_structure(&token.to_lowercase())
+        );
+    }
+
+    #[test]
+    fn mistyped_tokens_fail_structure_check() {
+        let token = token().replacen('A', "B", 1);
+        let expected = Err(Error::new(Kind::InvalidChecksum, "Don't care"));
+
+        assert!(PROFILE.looks_like_token(&token));
+        assert_eq!(expected, PROFILE.verify_structure(&token));
+    }
+
+    #[test]
+    #[should_panic]
+    fn short_entropy_panics() {
+        PROFILE.issue_from(&[0; 19]);
+    }
+}
@@ -0,0 +1,236 @@
+use core::{fmt, str::FromStr};
+
+use alloc::{borrow::ToOwned, string::String};
+
+use crate::{
+    decoding::decode_padded, encoding::encode_padded, error::Kind, Error, Result, Uuid7,
+    MAX_ENCODED_LEN_U128, UPPERCASE_ENCODING,
+};
+
+const MAX_PREFIX_LEN: usize = 63;
+
+/// A TypeID: a lowercase type prefix, an underscore and a UUID in 26 lowercase Crockford digits.
+///
+/// Parsing follows the TypeID spec to the letter, which is stricter than the rest of this crate:
+/// the suffix must be lowercase, ambiguous symbols such as `o` and `l` are not folded, and the
+/// prefix may only contain lowercase ASCII letters and underscores, neither starting nor ending
+/// with an underscore. An empty prefix is allowed, in which case the underscore is left off too.
+///
+/// The spec calls for version 7 UUIDs but accepts any, so nothing stops a TypeID read from
+/// elsewhere from holding another version; [`TypeId::uuid7`] checks.
+///
+/// ```rust
+/// # use crockford::{Error, TypeId, Uuid7};
+/// # fn run() -> Result<(), Error> {
+/// let id = TypeId::new("user", Uuid7::from_parts(1_571_000_000_000, 0x123, 0x456))?;
+/// let encoded = id.to_string();
+///
+/// assert!(encoded.starts_with("user_"));
+/// assert_eq!(id, encoded.parse()?);
+/// assert!("User_01h455vb4pex5vsknk084sn02q".parse::<TypeId>().is_err());
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TypeId {
+    prefix: String,
+    id: u128,
+}
+
+impl TypeId {
+    /// Creates a TypeID from a prefix and a UUID.
+    ///
+    /// Fails if the prefix does not follow the TypeID grammar.
+    pub fn new(prefix: &str, id: Uuid7) -> Result<TypeId> {
+        TypeId::from_u128(prefix, id.as_u128())
+    }
+
+    /// Creates a TypeID from a prefix and a UUID of any version.
+    ///
+    /// Fails if the prefix does not follow the TypeID grammar.
+    pub fn from_u128(prefix: &str, id: u128) -> Result<TypeId> {
+        check_prefix(prefix)?;
+        Ok(TypeId {
+            prefix: prefix.to_owned(),
+            id,
+        })
+    }
+
+    /// Creates a TypeID for a freshly generated UUID.
+    ///
+    /// Fails if the prefix does not follow the TypeID grammar.
+    #[cfg(feature = "rand")]
+    pub fn generate(prefix: &str) -> Result<TypeId> {
+        TypeId::new(prefix, Uuid7::now())
+    }
+
+    /// Returns the type prefix, which may be empty.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the UUID as a 128-bit value.
+    pub fn as_u128(&self) -> u128 {
+        self.id
+    }
+
+    /// Returns the UUID, provided it is version 7.
+    pub fn uuid7(&self) -> Result<Uuid7> {
+        Uuid7::from_u128(self.id)
+    }
+}
+
+impl fmt::Display for TypeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = String::with_capacity(self.prefix.len() + 1 + MAX_ENCODED_LEN_U128);
+        if !self.prefix.is_empty() {
+            s.push_str(&self.prefix);
+            s.push('_');
+        }
+
+        let mut suffix = String::with_capacity(MAX_ENCODED_LEN_U128);
+        encode_padded(self.id, MAX_ENCODED_LEN_U128, &mut suffix);
+        s.push_str(&suffix.to_ascii_lowercase());
+        f.pad(&s)
+    }
+}
+
+impl FromStr for TypeId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<TypeId> {
+        let (prefix, suffix) = match s.rsplit_once('_') {
+            Some(("", _)) => {
+                return Err(Error::new(
+                    Kind::InvalidFormat,
+                    "TypeID separator requires a prefix.",
+                ))
+            }
+            Some(parts) => parts,
+            None => ("", s),
+        };
+        check_prefix(prefix)?;
+
+        let offset = s.len() - suffix.len();
+        if let Some((idx, &u)) = suffix.as_bytes().iter().enumerate().find(|(_, u)| {
+            u.is_ascii_uppercase() || !UPPERCASE_ENCODING.contains(&u.to_ascii_uppercase())
+        }) {
+            return Err(Error::new(
+                Kind::InvalidDigit(offset + idx, u),
+                "Invalid TypeID suffix symbol.",
+            ));
+        }
+
+        let id = decode_padded(suffix, MAX_ENCODED_LEN_U128)?;
+        Ok(TypeId {
+            prefix: prefix.to_owned(),
+            id,
+        })
+    }
+}
+
+fn check_prefix(prefix: &str) -> Result<()> {
+    if prefix.len() > MAX_PREFIX_LEN {
+        return Err(Error::new(
+            Kind::InvalidFormat,
+            "TypeID prefix is too long.",
+        ));
+    }
+
+    if prefix.starts_with('_') || prefix.ends_with('_') {
+        return Err(Error::new(
+            Kind::InvalidFormat,
+            "TypeID prefix starts or ends with an underscore.",
+        ));
+    }
+
+    if !prefix.bytes().all(|u| u.is_ascii_lowercase() || u == b'_') {
+        return Err(Error::new(
+            Kind::InvalidFormat,
+            "TypeID prefix may only contain lowercase letters and underscores.",
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Kind, Error, TypeId};
+
+    // Cases from the valid and invalid lists in the TypeID spec.
+
+    #[test]
+    fn spec_examples_round_trip() {
+        let cases = [
+            ("", 0, "00000000000000000000000000"),
+            ("prefix", 1, "prefix_00000000000000000000000001"),
+            ("pre_fix", 0, "pre_fix_00000000000000000000000000"),
+            (
+                "prefix",
+                0x0189_0a5d_ac96_774b_bcce_b302_099a_8057,
+                "prefix_01h455vb4pex5vsknk084sn02q",
+            ),
+            ("", u128::MAX, "7zzzzzzzzzzzzzzzzzzzzzzzzz"),
+        ];
+
+        for &(prefix, id, encoded) in &cases {
+            let type_id = TypeId::from_u128(prefix, id).unwrap();
+            assert_eq!(encoded, type_id.to_string());
+            assert_eq!(Ok(type_id), encoded.parse());
+        }
+    }
+
+    #[test]
+    fn invalid_prefixes_fail() {
+        let expected = Err(Error::new(Kind::InvalidFormat, "Don't care"));
+
+        for s in &[
+            "PREFIX_00000000000000000000000000",
+            "12345_00000000000000000000000000",
+            "pre.fix_00000000000000000000000000",
+            "_prefix_00000000000000000000000000",
+            "prefix__00000000000000000000000000",
+            "_00000000000000000000000000",
+            "abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyzabcdefghijkl_00000000000000000000000000",
+        ] {
+            assert_eq!(expected, s.parse::<TypeId>(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn invalid_suffixes_fail() {
+        let invalid_digit = Err(Error::new(Kind::InvalidDigit(0, 0), "Don't care"));
+
+        assert_eq!(
+            invalid_digit,
+            "prefix_01H455VB4PEX5VSKNK084SN02Q".parse::<TypeId>()
+        );
+        assert_eq!(
+            invalid_digit,
+            "prefix_0123456789-123456789-123456".parse::<TypeId>()
+        );
+        assert_eq!(
+            invalid_digit,
+            "prefix_ooooooiiiiiiuuuuuuulllllll".parse::<TypeId>()
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidLength, "Don't care")),
+            "prefix_1234567890123456789012345".parse::<TypeId>()
+        );
+        assert_eq!(
+            Err(Error::new(Kind::OutOfRange, "Don't care")),
+            "8zzzzzzzzzzzzzzzzzzzzzzzzz".parse::<TypeId>()
+        );
+    }
+
+    #[test]
+    fn uuid7_is_checked_on_request() {
+        let nil = TypeId::from_u128("", 0).unwrap();
+        assert_eq!(
+            Err(Error::new(Kind::InvalidFormat, "Don't care")),
+            nil.uuid7()
+        );
+    }
+}
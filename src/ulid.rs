@@ -0,0 +1,269 @@
+use core::{fmt, str::FromStr};
+
+#[cfg(feature = "rand")]
+use rand::{rngs::ThreadRng, Rng};
+
+use crate::{
+    decoding::decode_padded,
+    encoding::{encode_padded, Digits},
+    Error, Result, MAX_ENCODED_LEN_U128,
+};
+
+const RANDOM_BITS: u32 = 80;
+const RANDOM_MASK: u128 = (1 << RANDOM_BITS) - 1;
+
+/// A ULID: a 48-bit Unix timestamp in milliseconds followed by 80 random bits, displayed as 26
+/// Crockford digits.
+///
+/// ULIDs sort by creation time, both as values and in their encoded form. Every 128-bit value is
+/// a valid ULID, but only encodings whose leading digit is `7` or lower fit in 128 bits, so
+/// parsing anything larger fails with `OutOfRange`.
+///
+/// ```rust
+/// # use crockford::{Error, Ulid};
+/// # fn run() -> Result<(), Error> {
+/// let id: Ulid = "01ARZ3NDEKTSV4RRFFQ69G5FAV".parse()?;
+///
+/// assert_eq!(1_469_922_850_259, id.unix_ms());
+/// assert_eq!("01ARZ3NDEKTSV4RRFFQ69G5FAV", id.to_string());
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ulid(u128);
+
+impl Ulid {
+    /// Assembles a ULID from a timestamp and random bits.
+    ///
+    /// Only the low 48 bits of the timestamp and the low 80 bits of `random` are used.
+    pub fn from_parts(unix_ms: u64, random: u128) -> Ulid {
+        let unix_ms = u128::from(unix_ms) & ((1 << 48) - 1);
+        Ulid(unix_ms << RANDOM_BITS | random & RANDOM_MASK)
+    }
+
+    /// Interprets a 128-bit value as a ULID.
+    pub fn from_u128(n: u128) -> Ulid {
+        Ulid(n)
+    }
+
+    /// Generates a ULID for the current time using the thread-local random number generator.
+    #[cfg(feature = "rand")]
+    pub fn now() -> Ulid {
+        Ulid::now_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a ULID for the current time using the provided random number generator.
+    #[cfg(feature = "rand")]
+    pub fn now_with_rng<R: rand::Rng + ?Sized>(rng: &mut R) -> Ulid {
+        Ulid::from_parts(crate::uuid7::unix_ms_now(), rng.gen())
+    }
+
+    /// Returns the timestamp, in milliseconds since the Unix epoch.
+    pub fn unix_ms(self) -> u64 {
+        (self.0 >> RANDOM_BITS) as u64
+    }
+
+    /// Returns the timestamp as a `SystemTime`.
+    ///
+    /// ULIDs only have room for 48 bits of milliseconds, so every timestamp falls before the
+    /// year 10890 and can be represented.
+    ///
+    /// ```rust
+    /// # use std::time::{Duration, UNIX_EPOCH};
+    /// # use crockford::{Error, Ulid};
+    /// # fn run() -> Result<(), Error> {
+    /// let id: Ulid = "01ARZ3NDEKTSV4RRFFQ69G5FAV".parse()?;
+    ///
+    /// assert_eq!(UNIX_EPOCH + Duration::from_millis(1_469_922_850_259), id.datetime());
+    /// # Ok(())
+    /// # }
+    /// # run().unwrap()
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn datetime(self) -> std::time::SystemTime {
+        std::time::UNIX_EPOCH + std::time::Duration::from_millis(self.unix_ms())
+    }
+
+    /// Returns the 80 random bits.
+    pub fn random(self) -> u128 {
+        self.0 & RANDOM_MASK
+    }
+
+    /// Returns the ULID as a 128-bit value.
+    pub fn as_u128(self) -> u128 {
+        self.0
+    }
+}
+
+/// Generates ULIDs for the current time from a random number generator of your choosing.
+///
+/// Unlike `Uuid7Generator`, this keeps no state between IDs, so IDs generated in the same
+/// millisecond sort in no particular order.
+///
+/// ```rust
+/// use crockford::{Ulid, UlidGenerator};
+///
+/// let ids: Vec<Ulid> = UlidGenerator::new().take(3).collect();
+///
+/// assert_eq!(3, ids.len());
+/// assert_ne!(ids[0], ids[1]);
+/// ```
+#[cfg(feature = "rand")]
+#[derive(Clone, Debug)]
+pub struct UlidGenerator<R = ThreadRng> {
+    rng: R,
+}
+
+#[cfg(feature = "rand")]
+impl UlidGenerator {
+    /// Creates a generator that uses the thread-local random number generator.
+    pub fn new() -> UlidGenerator {
+        UlidGenerator::with_rng(rand::thread_rng())
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Default for UlidGenerator {
+    fn default() -> UlidGenerator {
+        UlidGenerator::new()
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<R: Rng> UlidGenerator<R> {
+    /// Creates a generator that uses the provided random number generator.
+    pub fn with_rng(rng: R) -> UlidGenerator<R> {
+        UlidGenerator { rng }
+    }
+
+    /// Generates a ULID for the current time.
+    pub fn generate(&mut self) -> Ulid {
+        Ulid::now_with_rng(&mut self.rng)
+    }
+}
+
+/// Yields ULIDs for the current time, indefinitely.
+#[cfg(feature = "rand")]
+impl<R: Rng> Iterator for UlidGenerator<R> {
+    type Item = Ulid;
+
+    fn next(&mut self) -> Option<Ulid> {
+        Some(self.generate())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl fmt::Display for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut digits = Digits::<MAX_ENCODED_LEN_U128>::new();
+        encode_padded(self.0, MAX_ENCODED_LEN_U128, &mut digits);
+        f.pad(digits.as_str())
+    }
+}
+
+impl FromStr for Ulid {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Ulid> {
+        decode_padded(s, MAX_ENCODED_LEN_U128).map(Ulid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Kind, Error, Ulid};
+
+    #[test]
+    fn parts_are_laid_out_per_spec() {
+        let id = Ulid::from_parts(0x0123_4567_89AB, 0xCDEF);
+
+        assert_eq!(0x0123_4567_89AB_0000_0000_0000_0000_CDEF, id.as_u128());
+        assert_eq!(0x0123_4567_89AB, id.unix_ms());
+        assert_eq!(0xCDEF, id.random());
+    }
+
+    #[test]
+    fn oversized_parts_are_masked() {
+        let id = Ulid::from_parts(u64::MAX, u128::MAX);
+
+        assert_eq!(u128::MAX, id.as_u128());
+        assert_eq!("7ZZZZZZZZZZZZZZZZZZZZZZZZZ", id.to_string());
+    }
+
+    #[test]
+    fn encoded_form_round_trips() {
+        let id = Ulid::from_parts(1_469_922_850_259, 0x1234_5678_9ABC_DEF0_1234);
+        let encoded = id.to_string();
+
+        assert_eq!(26, encoded.len());
+        assert!(encoded.starts_with("01ARZ3NDEK"));
+        assert_eq!(Ok(id), encoded.parse());
+        assert_eq!(Ok(id), encoded.to_lowercase().parse());
+    }
+
+    #[test]
+    fn ids_sort_by_time() {
+        let earlier = Ulid::from_parts(1000, u128::MAX);
+        let later = Ulid::from_parts(1001, 0);
+
+        assert!(earlier < later);
+        assert!(earlier.to_string() < later.to_string());
+    }
+
+    #[test]
+    fn overflowing_input_fails() {
+        assert_eq!(
+            Err(Error::new(Kind::OutOfRange, "Don't care")),
+            "80000000000000000000000000".parse::<Ulid>()
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidLength, "Don't care")),
+            "01ARZ3NDEKTSV4RRFFQ69G5FA".parse::<Ulid>()
+        );
+        assert_eq!(
+            Err(Error::new(Kind::InvalidDigit(0, 0), "Don't care")),
+            "01ARZ3NDEKTSV4RRFFQ69G5FA!".parse::<Ulid>()
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn datetimes_cover_the_whole_timestamp_range() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        assert_eq!(UNIX_EPOCH, Ulid::from_parts(0, u128::MAX).datetime());
+        assert_eq!(
+            UNIX_EPOCH + Duration::from_millis(0xFFFF_FFFF_FFFF),
+            Ulid::from_u128(u128::MAX).datetime()
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generated_ids_carry_the_time() {
+        let before = crate::uuid7::unix_ms_now();
+        let id = Ulid::now();
+
+        assert!(id.unix_ms() >= before);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generators_yield_indefinitely() {
+        use rand::{rngs::mock::StepRng, Rng};
+
+        let mut rng = StepRng::new(0, 1 << 40);
+        let expected: Vec<u128> = (0..1000)
+            .map(|_| rng.gen::<u128>() & super::RANDOM_MASK)
+            .collect();
+        let generator = crate::UlidGenerator::with_rng(StepRng::new(0, 1 << 40));
+
+        assert_eq!((usize::MAX, None), generator.size_hint());
+        let random: Vec<_> = generator.take(1000).map(Ulid::random).collect();
+        assert_eq!(expected, random);
+    }
+}
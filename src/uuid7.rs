@@ -0,0 +1,364 @@
+use core::{fmt, str::FromStr};
+
+#[cfg(feature = "rand")]
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    decoding::decode_padded,
+    encoding::{encode_padded, Digits},
+    error::Kind,
+    Error, Result, MAX_ENCODED_LEN_U128,
+};
+
+const VERSION: u128 = 0x7 << 76;
+const VERSION_MASK: u128 = 0xF << 76;
+const VARIANT: u128 = 0b10 << 62;
+const VARIANT_MASK: u128 = 0b11 << 62;
+
+/// A version 7 UUID, as laid out by RFC 9562, that displays as 26 Crockford digits.
+///
+/// The leading 48 bits are a Unix timestamp in milliseconds, so these sort by creation time.
+/// The rest is version and variant bits plus 74 bits of randomness.
+///
+/// ```rust
+/// # use crockford::{Error, Uuid7};
+/// # fn run() -> Result<(), Error> {
+/// let id = Uuid7::from_parts(1_571_000_000_000, 0x123, 0x456);
+/// let encoded = id.to_string();
+///
+/// assert_eq!(26, encoded.len());
+/// assert_eq!(1_571_000_000_000, id.unix_ms());
+/// assert_eq!(id, encoded.to_lowercase().parse()?);
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uuid7(u128);
+
+impl Uuid7 {
+    /// Assembles a UUID from a timestamp and random bits.
+    ///
+    /// Only the low 48 bits of the timestamp, 12 bits of `rand_a` and 62 bits of `rand_b` are
+    /// used.
+    pub fn from_parts(unix_ms: u64, rand_a: u16, rand_b: u64) -> Uuid7 {
+        let unix_ms = u128::from(unix_ms) & ((1 << 48) - 1);
+        let rand_a = u128::from(rand_a) & 0xFFF;
+        let rand_b = u128::from(rand_b) & ((1 << 62) - 1);
+
+        Uuid7(unix_ms << 80 | VERSION | rand_a << 64 | VARIANT | rand_b)
+    }
+
+    /// Attempts to interpret a 128-bit value as a version 7 UUID.
+    ///
+    /// Fails if the version or variant bits are wrong.
+    pub fn from_u128(n: u128) -> Result<Uuid7> {
+        if n & VERSION_MASK == VERSION && n & VARIANT_MASK == VARIANT {
+            Ok(Uuid7(n))
+        } else {
+            Err(Error::new(
+                Kind::InvalidFormat,
+                "Value is not a version 7 UUID.",
+            ))
+        }
+    }
+
+    /// Generates a UUID for the current time using the thread-local random number generator.
+    #[cfg(feature = "rand")]
+    pub fn now() -> Uuid7 {
+        Uuid7::now_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a UUID for the current time using the provided random number generator.
+    #[cfg(feature = "rand")]
+    pub fn now_with_rng<R: rand::Rng + ?Sized>(rng: &mut R) -> Uuid7 {
+        Uuid7::from_parts(unix_ms_now(), rng.gen(), rng.gen())
+    }
+
+    /// Returns the timestamp, in milliseconds since the Unix epoch.
+    pub fn unix_ms(self) -> u64 {
+        (self.0 >> 80) as u64
+    }
+
+    /// Returns the UUID as a 128-bit value.
+    pub fn as_u128(self) -> u128 {
+        self.0
+    }
+
+    /// Returns the standard hyphenated lowercase hex form of the UUID.
+    #[cfg(feature = "uuid")]
+    pub fn to_hyphenated(self) -> String {
+        uuid::Uuid::from(self).hyphenated().to_string()
+    }
+
+    /// Attempts to parse a UUID written in any of the standard hex forms.
+    #[cfg(feature = "uuid")]
+    pub fn parse_hyphenated(input: &str) -> Result<Uuid7> {
+        let uuid = uuid::Uuid::parse_str(input)
+            .map_err(|_| Error::new(Kind::InvalidFormat, "Input is not a hex UUID."))?;
+        Uuid7::from_u128(uuid.as_u128())
+    }
+}
+
+/// Generates strictly increasing version 7 UUIDs, shared between any number of threads.
+///
+/// The 12 bits after the timestamp hold a sequence number rather than random bits, as RFC 9562
+/// allows, so that UUIDs generated in the same millisecond still sort in the order they were
+/// generated. The timestamp and sequence number are packed into a single atomic and advanced with
+/// a compare-and-swap loop, so no lock is needed. If more than 4096 UUIDs are generated in one
+/// millisecond, the timestamp runs ahead of the clock until the clock catches up; if the clock
+/// moves backwards, the generator carries on from the last timestamp it used.
+///
+/// ```rust
+/// use crockford::Uuid7Generator;
+///
+/// static GENERATOR: Uuid7Generator = Uuid7Generator::new();
+///
+/// let first = GENERATOR.generate();
+/// let second = std::thread::spawn(|| GENERATOR.generate()).join().unwrap();
+///
+/// assert!(first < second);
+/// ```
+#[cfg(feature = "rand")]
+#[derive(Debug, Default)]
+pub struct Uuid7Generator {
+    /// The timestamp of the last UUID, shifted left by 12 bits, plus its sequence number.
+    state: AtomicU64,
+}
+
+#[cfg(feature = "rand")]
+impl Uuid7Generator {
+    /// Creates a generator.
+    pub const fn new() -> Uuid7Generator {
+        Uuid7Generator {
+            state: AtomicU64::new(0),
+        }
+    }
+
+    /// Generates a UUID for the current time.
+    pub fn generate(&self) -> Uuid7 {
+        self.generate_at(unix_ms_now())
+    }
+
+    /// Generates a UUID for the provided time, in milliseconds since the Unix epoch.
+    ///
+    /// The UUID is greater than any this generator has produced before, whatever the time.
+    pub fn generate_at(&self, unix_ms: u64) -> Uuid7 {
+        use rand::Rng;
+
+        let floor = (unix_ms & ((1 << 48) - 1)) << 12;
+        let next = |last: u64| std::cmp::max(floor, last + 1);
+
+        // A single atomic only ever moves forward, so relaxed ordering is enough.
+        let last = self
+            .state
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+                Some(next(last))
+            })
+            .expect("the update always succeeds");
+        let state = next(last);
+
+        Uuid7::from_parts(
+            state >> 12,
+            (state & 0xFFF) as u16,
+            rand::thread_rng().gen(),
+        )
+    }
+}
+
+/// Yields UUIDs for the current time, indefinitely.
+#[cfg(feature = "rand")]
+impl Iterator for Uuid7Generator {
+    type Item = Uuid7;
+
+    fn next(&mut self) -> Option<Uuid7> {
+        Some(self.generate())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Yields UUIDs for the current time, indefinitely, from a shared generator.
+#[cfg(feature = "rand")]
+impl Iterator for &Uuid7Generator {
+    type Item = Uuid7;
+
+    fn next(&mut self) -> Option<Uuid7> {
+        Some(self.generate())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[cfg(feature = "rand")]
+pub(crate) fn unix_ms_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl fmt::Display for Uuid7 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut digits = Digits::<MAX_ENCODED_LEN_U128>::new();
+        encode_padded(self.0, MAX_ENCODED_LEN_U128, &mut digits);
+        f.pad(digits.as_str())
+    }
+}
+
+impl FromStr for Uuid7 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Uuid7> {
+        decode_padded(s, MAX_ENCODED_LEN_U128).and_then(Uuid7::from_u128)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<Uuid7> for uuid::Uuid {
+    fn from(id: Uuid7) -> uuid::Uuid {
+        uuid::Uuid::from_u128(id.0)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl core::convert::TryFrom<uuid::Uuid> for Uuid7 {
+    type Error = Error;
+
+    fn try_from(uuid: uuid::Uuid) -> Result<Uuid7> {
+        Uuid7::from_u128(uuid.as_u128())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Kind, Error, Uuid7};
+
+    #[test]
+    fn parts_are_laid_out_per_rfc() {
+        let id = Uuid7::from_parts(0x0123_4567_89AB, 0xCDE, 0x3FFF_FFFF_FFFF_FFFF);
+
+        assert_eq!(0x0123_4567_89AB_7CDE_BFFF_FFFF_FFFF_FFFF, id.as_u128());
+        assert_eq!(0x0123_4567_89AB, id.unix_ms());
+    }
+
+    #[test]
+    fn oversized_parts_are_masked() {
+        let id = Uuid7::from_parts(u64::MAX, u16::MAX, u64::MAX);
+        assert_eq!(Ok(id), Uuid7::from_u128(id.as_u128()));
+    }
+
+    #[test]
+    fn encoded_form_round_trips() {
+        let id = Uuid7::from_parts(1_571_000_000_000, 0x123, 0x456);
+        let encoded = id.to_string();
+
+        assert_eq!(26, encoded.len());
+        assert_eq!(Ok(id), encoded.parse());
+    }
+
+    #[test]
+    fn ids_sort_by_time() {
+        let earlier = Uuid7::from_parts(1000, 0xFFF, u64::MAX);
+        let later = Uuid7::from_parts(1001, 0, 0);
+
+        assert!(earlier < later);
+        assert!(earlier.to_string() < later.to_string());
+    }
+
+    #[test]
+    fn other_versions_fail() {
+        let expected = Err(Error::new(Kind::InvalidFormat, "Don't care"));
+
+        // A version 4 UUID.
+        assert_eq!(
+            expected,
+            Uuid7::from_u128(0x1234_5678_9ABC_4DEF_8123_4567_89AB_CDEF)
+        );
+        assert_eq!(expected, "00000000000000000000000000".parse::<Uuid7>());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generated_ids_are_valid() {
+        let id = Uuid7::now();
+        assert_eq!(Ok(id), Uuid7::from_u128(id.as_u128()));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generated_ids_increase_within_a_millisecond() {
+        let generator = crate::Uuid7Generator::new();
+        let ids: Vec<_> = (0..5000).map(|_| generator.generate_at(1000)).collect();
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(1000, ids[0].unix_ms());
+        assert_eq!(1001, ids[4999].unix_ms());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generators_yield_increasing_ids() {
+        let generator = crate::Uuid7Generator::new();
+        let mut ids: Vec<_> = (&generator).take(1000).collect();
+        ids.extend(generator.take(1000));
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generated_ids_survive_clock_rollback() {
+        let generator = crate::Uuid7Generator::new();
+        let first = generator.generate_at(2000);
+        let second = generator.generate_at(1000);
+
+        assert!(first < second);
+        assert_eq!(2000, second.unix_ms());
+        assert_eq!(3000, generator.generate_at(3000).unix_ms());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn shared_generators_never_repeat() {
+        use std::{collections::HashSet, thread};
+
+        let generator = crate::Uuid7Generator::new();
+        let ids: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let ids: Vec<_> = (0..1000).map(|_| generator.generate_at(1000)).collect();
+                        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+                        ids
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+
+        let unique: HashSet<_> = ids.iter().map(|id| id.as_u128() >> 62).collect();
+        assert_eq!(4000, unique.len());
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn hex_form_round_trips() {
+        let id = Uuid7::from_parts(0x0123_4567_89AB, 0xCDE, 0x3FFF_FFFF_FFFF_FFFF);
+        let hex = id.to_hyphenated();
+
+        assert_eq!("01234567-89ab-7cde-bfff-ffffffffffff", hex);
+        assert_eq!(Ok(id), Uuid7::parse_hyphenated(&hex));
+        assert_eq!(Ok(id), Uuid7::parse_hyphenated(&hex.to_uppercase()));
+    }
+}
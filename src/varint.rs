@@ -0,0 +1,152 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{decode, encode_into, error::Kind, Error, Result};
+
+/// Separates values in the text form of a varint stream.
+const SEPARATOR: char = '-';
+
+/// Renders a stream of unsigned LEB128 varints as hyphen-separated Crockford values.
+///
+/// Fails if the stream ends in the middle of a varint or if any varint overflows a `u64`. Varints
+/// padded with redundant trailing zero groups, such as `[0x80, 0x00]` for zero, are rejected
+/// too, so that every accepted stream converts back exactly.
+///
+/// ```rust
+/// # use crockford::Error;
+/// # fn run() -> Result<(), Error> {
+/// // 5111 and 1 as LEB128.
+/// let text = crockford::from_leb128(&[0xF7, 0x27, 0x01])?;
+///
+/// assert_eq!("4ZQ-1", text);
+/// assert_eq!(vec![0xF7, 0x27, 0x01], crockford::to_leb128(&text)?);
+/// # Ok(())
+/// # }
+/// # run().unwrap()
+/// ```
+pub fn from_leb128(bytes: &[u8]) -> Result<String> {
+    let mut text = String::with_capacity(bytes.len() * 2);
+    let mut n: u64 = 0;
+    let mut shift = 0;
+
+    for &u in bytes {
+        let group = u64::from(u & 0x7F);
+        if shift >= 64 || (shift > 0 && group >> (64 - shift) != 0) {
+            return Err(Error::new(Kind::OutOfRange, "Varint is too large"));
+        }
+
+        if u & 0x80 == 0 && shift > 0 && group == 0 {
+            return Err(Error::new(
+                Kind::InvalidFormat,
+                "Varint is not minimally encoded",
+            ));
+        }
+
+        n |= group << shift;
+        shift += 7;
+
+        if u & 0x80 == 0 {
+            if !text.is_empty() {
+                text.push(SEPARATOR);
+            }
+            encode_into(n, &mut text);
+            n = 0;
+            shift = 0;
+        }
+    }
+
+    if shift != 0 {
+        return Err(Error::new(
+            Kind::InvalidLength,
+            "Varint stream is truncated",
+        ));
+    }
+
+    Ok(text)
+}
+
+/// Parses hyphen-separated Crockford values back into a stream of unsigned LEB128 varints.
+pub fn to_leb128<T: AsRef<str>>(input: T) -> Result<Vec<u8>> {
+    let input = input.as_ref();
+    let mut bytes = Vec::with_capacity(input.len());
+
+    if input.is_empty() {
+        return Ok(bytes);
+    }
+
+    for value in input.split(SEPARATOR) {
+        let mut n = decode(value)?;
+        loop {
+            let u = (n & 0x7F) as u8;
+            n >>= 7;
+
+            if n == 0 {
+                bytes.push(u);
+                break;
+            }
+            bytes.push(u | 0x80);
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::Kind, from_leb128, to_leb128, Error};
+
+    #[test]
+    fn values_round_trip() {
+        let text = "0-1-3Z-40-4ZQ-FZZZZZZZZZZZZ";
+        let bytes = to_leb128(text).unwrap();
+
+        assert_eq!(Ok(text.to_owned()), from_leb128(&bytes));
+    }
+
+    #[test]
+    fn max_value_is_ten_bytes() {
+        let bytes = to_leb128("fzzzzzzzzzzzz").unwrap();
+
+        assert_eq!(10, bytes.len());
+        assert_eq!(0x01, bytes[9]);
+    }
+
+    #[test]
+    fn empty_streams_are_empty() {
+        assert_eq!(Ok(String::new()), from_leb128(&[]));
+        assert_eq!(Ok(Vec::new()), to_leb128(""));
+    }
+
+    #[test]
+    fn truncated_streams_fail() {
+        let expected = Err(Error::new(Kind::InvalidLength, "Don't care"));
+        assert_eq!(expected, from_leb128(&[0xF7, 0xA7]));
+    }
+
+    #[test]
+    fn overflowing_varints_fail() {
+        let expected = Err(Error::new(Kind::OutOfRange, "Don't care"));
+
+        let mut bytes = vec![0xFF; 9];
+        bytes.push(0x02);
+        assert_eq!(expected, from_leb128(&bytes));
+
+        let mut bytes = vec![0x80; 10];
+        bytes.push(0x00);
+        assert_eq!(expected, from_leb128(&bytes));
+    }
+
+    #[test]
+    fn non_minimal_varints_fail() {
+        let expected = Err(Error::new(Kind::InvalidFormat, "Don't care"));
+
+        assert_eq!(expected, from_leb128(&[0x80, 0x00]));
+        assert_eq!(expected, from_leb128(&[0x01, 0xF7, 0xA7, 0x00]));
+        assert_eq!(Ok("0".to_owned()), from_leb128(&[0x00]));
+    }
+
+    #[test]
+    fn invalid_values_fail() {
+        assert!(to_leb128("4ZQ--1").is_err());
+        assert!(to_leb128("4ZQ-!").is_err());
+    }
+}